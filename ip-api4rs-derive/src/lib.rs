@@ -0,0 +1,292 @@
+//! # IP-API4rs derive
+//!
+//! Derive macros for [ip-api4rs](https://docs.rs/ip-api4rs).
+//! Enable the `derive` feature of `ip-api4rs` instead of depending on this crate directly.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, LitStr, Token};
+
+/// The fields of the API with their bit in the numeric `fields` parameter.
+/// This has to match `ip_api4rs::field::Field`.
+const FIELDS: [(&str, u32); 25] = [
+    ("status", 1 << 14),
+    ("message", 1 << 15),
+    ("continent", 1 << 20),
+    ("continentCode", 1 << 21),
+    ("country", 1 << 0),
+    ("countryCode", 1 << 1),
+    ("region", 1 << 2),
+    ("regionName", 1 << 3),
+    ("city", 1 << 4),
+    ("district", 1 << 19),
+    ("zip", 1 << 5),
+    ("lat", 1 << 6),
+    ("lon", 1 << 7),
+    ("timezone", 1 << 8),
+    ("offset", 1 << 25),
+    ("currency", 1 << 23),
+    ("isp", 1 << 9),
+    ("org", 1 << 10),
+    ("as", 1 << 11),
+    ("asname", 1 << 22),
+    ("reverse", 1 << 12),
+    ("mobile", 1 << 16),
+    ("proxy", 1 << 17),
+    ("hosting", 1 << 24),
+    ("query", 1 << 13),
+];
+
+/// Derives `ip_api4rs::model::ip_api_model::IpApiModel` for a struct with named fields.
+///
+/// The name of every field, after `#[serde(rename)]` and `#[serde(rename_all)]`, has to be a field of the API.
+/// Fields marked with `#[serde(skip)]` or `#[serde(skip_deserializing)]` are not requested.
+/// Fields marked with `#[serde(flatten)]` request the fields of their type, which has to implement the trait too.
+///
+/// # Example
+/// ```
+/// use ip_api4rs::model::ip_api_model::IpApiModel;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, IpApiModel)]
+/// #[serde(rename_all = "camelCase")]
+/// struct Location {
+///     country_code: String,
+///     #[serde(rename = "query")]
+///     ip: String,
+/// }
+///
+/// assert_eq!(Location::FIELDS_PARAM, "40962");
+/// ```
+///
+/// A typo in a field name fails to compile:
+/// ```compile_fail
+/// use ip_api4rs::model::ip_api_model::IpApiModel;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, IpApiModel)]
+/// struct Location {
+///     contry: String,
+/// }
+/// ```
+#[proc_macro_derive(IpApiModel, attributes(serde))]
+pub fn derive_ip_api_model(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Expands the derive into the implementation of the trait.
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            Fields::Unit => return Err(syn::Error::new_spanned(&input.ident, "IpApiModel needs named fields")),
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(&input.ident, "IpApiModel can not be derived for tuple structs"))
+            }
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "IpApiModel can only be derived for structs")),
+    };
+
+    let rename_all = container_rename_rule(&input)?;
+    let mut mask = 0;
+    let mut flattened = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    for field in fields {
+        let attributes = field_attributes(field)?;
+        if attributes.skip {
+            continue;
+        }
+        if attributes.flatten {
+            flattened.push(&field.ty);
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named fields have an ident");
+        let name = match attributes.rename {
+            Some(rename) => rename.value(),
+            None => rename_all.apply(ident.to_string().trim_start_matches("r#")),
+        };
+        match FIELDS.iter().find(|(field_name, _)| *field_name == name) {
+            Some((_, bit)) => mask |= bit,
+            None => {
+                let error = syn::Error::new_spanned(field, unknown_field_message(&name));
+                match errors.as_mut() {
+                    Some(errors) => errors.combine(error),
+                    None => errors = Some(error),
+                }
+            }
+        }
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::ip_api4rs::model::ip_api_model::IpApiModel for #ident #ty_generics #where_clause {
+            const FIELDS: ::ip_api4rs::field::FieldSet = ::ip_api4rs::field::FieldSet::from_bits(
+                #mask #(| <#flattened as ::ip_api4rs::model::ip_api_model::IpApiModel>::FIELDS.bits())*
+            );
+        }
+    })
+}
+
+/// Builds the error message for a name the API does not know, with the closest field as suggestion.
+fn unknown_field_message(name: &str) -> String {
+    let closest = FIELDS
+        .iter()
+        .map(|(field_name, _)| (*field_name, edit_distance(name, field_name)))
+        .filter(|(_, distance)| *distance <= 2)
+        .min_by_key(|(_, distance)| *distance);
+    match closest {
+        Some((field_name, _)) => format!("`{}` is not a field of the API, did you mean `{}`?", name, field_name),
+        None => format!("`{}` is not a field of the API", name),
+    }
+}
+
+/// Computes the Levenshtein distance between two names.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// The serde attributes of a field that change the name it is deserialized from.
+#[derive(Default)]
+struct FieldAttributes {
+    rename: Option<LitStr>,
+    skip: bool,
+    flatten: bool,
+}
+
+/// Reads the serde attributes of a field.
+fn field_attributes(field: &syn::Field) -> syn::Result<FieldAttributes> {
+    let mut attributes = FieldAttributes::default();
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                if let Some(rename) = deserialize_name(&meta)? {
+                    attributes.rename = Some(rename);
+                }
+            } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                attributes.skip = true;
+            } else if meta.path.is_ident("flatten") {
+                attributes.flatten = true;
+            } else {
+                skip_meta(&meta)?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attributes)
+}
+
+/// Reads the `rename_all` rule of a struct.
+fn container_rename_rule(input: &DeriveInput) -> syn::Result<RenameRule> {
+    let mut rule = RenameRule::None;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                if let Some(name) = deserialize_name(&meta)? {
+                    rule = RenameRule::from_name(&name.value())
+                        .ok_or_else(|| syn::Error::new_spanned(&name, "unknown rename rule"))?;
+                }
+            } else {
+                skip_meta(&meta)?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(rule)
+}
+
+/// Reads the name used for deserialization from `key = "name"` or `key(deserialize = "name")`.
+fn deserialize_name(meta: &ParseNestedMeta) -> syn::Result<Option<LitStr>> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+    let mut name = None;
+    meta.parse_nested_meta(|nested| {
+        if nested.path.is_ident("deserialize") {
+            name = Some(nested.value()?.parse()?);
+        } else {
+            skip_meta(&nested)?;
+        }
+        Ok(())
+    })?;
+    Ok(name)
+}
+
+/// Skips a serde attribute that does not change the name, like `default` or `with = "module"`.
+fn skip_meta(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|nested| skip_meta(&nested))?;
+    }
+    Ok(())
+}
+
+/// The `rename_all` rules of serde, applied to snake case field names.
+enum RenameRule {
+    None,
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    /// Gets the rule with the name serde uses for it.
+    fn from_name(name: &str) -> Option<RenameRule> {
+        match name {
+            "lowercase" => Some(RenameRule::LowerCase),
+            "UPPERCASE" => Some(RenameRule::UpperCase),
+            "PascalCase" => Some(RenameRule::PascalCase),
+            "camelCase" => Some(RenameRule::CamelCase),
+            "snake_case" => Some(RenameRule::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Some(RenameRule::ScreamingSnakeCase),
+            "kebab-case" => Some(RenameRule::KebabCase),
+            "SCREAMING-KEBAB-CASE" => Some(RenameRule::ScreamingKebabCase),
+            _ => None,
+        }
+    }
+
+    /// Applies the rule to the name of a field.
+    fn apply(&self, field: &str) -> String {
+        match self {
+            RenameRule::None | RenameRule::SnakeCase => field.to_string(),
+            RenameRule::LowerCase => field.to_ascii_lowercase(),
+            RenameRule::UpperCase => field.to_ascii_uppercase(),
+            RenameRule::PascalCase => field.split('_').map(capitalize).collect(),
+            RenameRule::CamelCase => {
+                let pascal = RenameRule::PascalCase.apply(field);
+                let mut chars = pascal.chars();
+                chars.next().map(|first| first.to_ascii_lowercase().to_string() + chars.as_str()).unwrap_or_default()
+            }
+            RenameRule::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameRule::KebabCase => field.replace('_', "-"),
+            RenameRule::ScreamingKebabCase => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// Capitalizes the first character of a word.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+}
@@ -0,0 +1,444 @@
+//! A module that contains the builder for the clients.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use ip_api4rs::IpApiClient;
+//! use ip_api4rs::language::Language;
+//!
+//! let client = IpApiClient::builder()
+//!     .timeout(Duration::from_secs(5))
+//!     .language(Language::German)
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use governor::{Quota, RateLimiter};
+use nonzero_ext::nonzero;
+use reqwest::Client;
+
+#[cfg(feature = "blocking")]
+use crate::blocking::client::BlockingIpApiClient;
+use crate::cache::memory::MemoryCache;
+use crate::cache::ResponseCache;
+use crate::error::IpApiError;
+use crate::format::ResponseFormat;
+use crate::language::Language;
+use crate::util::rate_limit::{RateLimitHandling, ServerRateLimit};
+use crate::util::retry::RetryPolicy;
+use crate::IpApiClient;
+
+/// A builder for the `IpApiClient` and the `BlockingIpApiClient`.
+/// The combination of the settings is validated when the client is built.
+///
+/// The type parameter is the client that is built, so `IpApiClient::builder()` only builds an `IpApiClient`
+/// and `BlockingIpApiClient::builder()` only builds a `BlockingIpApiClient`.
+pub struct IpApiClientBuilder<C = IpApiClient> {
+    settings: Settings,
+    client: PhantomData<fn() -> C>,
+}
+
+/// A builder for the `BlockingIpApiClient`.
+#[cfg(feature = "blocking")]
+pub type BlockingIpApiClientBuilder = IpApiClientBuilder<BlockingIpApiClient>;
+
+impl<C> Default for IpApiClientBuilder<C> {
+    fn default() -> Self {
+        Self {
+            settings: Settings::default(),
+            client: PhantomData,
+        }
+    }
+}
+
+impl<C> Debug for IpApiClientBuilder<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IpApiClientBuilder").field("settings", &self.settings).finish()
+    }
+}
+
+impl IpApiClientBuilder {
+    /// Creates a new IpApiClientBuilder with the defaults of the free tier.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<C> IpApiClientBuilder<C> {
+    /// Sets the API key.
+    /// With an API key all requests are sent to the pro tier, which has no rate limit by default.
+    ///
+    /// # Arguments
+    /// * `api_key` - The API key of the pro tier.
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.settings.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Sets the quota for single requests.
+    /// Defaults to 45 requests per minute on the free tier and no quota on the pro tier.
+    ///
+    /// # Arguments
+    /// * `quota` - The quota for single requests.
+    pub fn quota(mut self, quota: Quota) -> Self {
+        self.settings.quota = Some(Some(quota));
+        self
+    }
+
+    /// Sets the quota for batch requests.
+    /// Defaults to 15 requests per minute on the free tier and no quota on the pro tier.
+    ///
+    /// # Arguments
+    /// * `quota` - The quota for batch requests.
+    pub fn batch_quota(mut self, quota: Quota) -> Self {
+        self.settings.batch_quota = Some(Some(quota));
+        self
+    }
+
+    /// Disables the local quotas for single and batch requests.
+    /// The rate limit reported by the API is still respected.
+    pub fn without_rate_limit(mut self) -> Self {
+        self.settings.quota = Some(None);
+        self.settings.batch_quota = Some(None);
+        self
+    }
+
+    /// Sets the timeout of a whole request.
+    ///
+    /// # Arguments
+    /// * `timeout` - The timeout of a request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.settings.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for connecting to the API.
+    ///
+    /// # Arguments
+    /// * `timeout` - The timeout of the connect phase.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.settings.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the `User-Agent` header of the requests.
+    ///
+    /// # Arguments
+    /// * `user_agent` - The user agent.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.settings.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets a custom base url of the API.
+    ///
+    /// # Arguments
+    /// * `base_url` - The base url, like `http://localhost:8080`.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.settings.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Sets a custom base url of the EDNS endpoint.
+    ///
+    /// # Arguments
+    /// * `edns_base_url` - The base url, like `http://localhost:8080`.
+    pub fn edns_base_url(mut self, edns_base_url: impl Into<String>) -> Self {
+        self.settings.edns_base_url = Some(edns_base_url.into());
+        self
+    }
+
+    /// Requires every request to be sent over HTTPS.
+    /// This is an assertion on the other settings, it does not change any url: building the client fails
+    /// unless the requests already go to the pro tier or to a HTTPS base url.
+    /// The free tier of ip-api.com does not support HTTPS, so this needs an API key or a HTTPS base url.
+    /// A custom EDNS base url has to use HTTPS too, the default EDNS endpoint is only reachable over HTTP.
+    ///
+    /// # Arguments
+    /// * `https` - Whether HTTPS is required.
+    pub fn https(mut self, https: bool) -> Self {
+        self.settings.https = https;
+        self
+    }
+
+    /// Sets the format the API answers single requests in.
+    /// The responses are decoded into the same models, batch requests are always answered in JSON.
+    ///
+    /// # Arguments
+    /// * `response_format` - The format of the responses.
+    pub fn response_format(mut self, response_format: ResponseFormat) -> Self {
+        self.settings.response_format = response_format;
+        self
+    }
+
+    /// Sets the default language of the responses.
+    ///
+    /// # Arguments
+    /// * `language` - The default language.
+    pub fn language(mut self, language: Language) -> Self {
+        self.settings.language = Some(language);
+        self
+    }
+
+    /// Sets how requests the API rejected because the rate limit is exceeded are handled.
+    ///
+    /// # Arguments
+    /// * `handling` - The handling of an exceeded rate limit.
+    pub fn rate_limit_handling(mut self, handling: RateLimitHandling) -> Self {
+        self.settings.rate_limit_handling = handling;
+        self
+    }
+
+    /// Sets the policy for retrying requests that failed for transient reasons.
+    /// Defaults to no retries.
+    ///
+    /// # Arguments
+    /// * `policy` - The retry policy.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.settings.retry_policy = policy;
+        self
+    }
+
+    /// Sets whether IP addresses in private and reserved ranges are rejected without sending a request.
+    /// The API can not locate them anyway, so the check saves quota and a round trip. Enabled by default.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether the local range check is enabled.
+    pub fn reserved_range_check(mut self, enabled: bool) -> Self {
+        self.settings.reserved_range_check = Some(enabled);
+        self
+    }
+
+    /// Enables the in-memory cache for the responses of single requests.
+    ///
+    /// # Arguments
+    /// * `ttl` - How long a response stays valid.
+    /// * `max_entries` - The maximum amount of cached responses.
+    pub fn cache(mut self, ttl: Duration, max_entries: usize) -> Self {
+        self.settings.cache = Some(Box::new(MemoryCache::new(ttl, max_entries)));
+        self
+    }
+
+    /// Sets a custom cache for the responses of single requests, like the on-disk `FileCache`.
+    ///
+    /// # Arguments
+    /// * `cache` - The cache to consult before sending a request.
+    pub fn response_cache(mut self, cache: impl ResponseCache + 'static) -> Self {
+        self.settings.cache = Some(Box::new(cache));
+        self
+    }
+}
+
+impl IpApiClientBuilder<IpApiClient> {
+    /// Sets a custom http client for the `IpApiClient`.
+    /// Timeouts and the user agent have to be configured on the custom client itself.
+    ///
+    /// # Arguments
+    /// * `client` - The http client.
+    pub fn http_client(mut self, client: Client) -> Self {
+        self.settings.http_client = Some(client);
+        self
+    }
+
+    /// Builds the `IpApiClient`.
+    ///
+    /// # Returns
+    /// * `Result<IpApiClient, IpApiError>` - The client or `IpApiError::InvalidConfiguration`.
+    pub fn build(self) -> Result<IpApiClient, IpApiError> {
+        let settings = self.settings;
+        settings.validate()?;
+        let client = match settings.http_client {
+            Some(client) => client,
+            None => {
+                let mut builder = Client::builder();
+                if let Some(timeout) = settings.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = settings.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(user_agent) = &settings.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                builder.build()?
+            }
+        };
+        Ok(IpApiClient {
+            client,
+            limiter: settings.quota.unwrap_or_else(|| default_quota(&settings.api_key)).map(RateLimiter::direct),
+            batch_limiter: settings
+                .batch_quota
+                .unwrap_or_else(|| default_batch_quota(&settings.api_key))
+                .map(RateLimiter::direct),
+            server_limit: ServerRateLimit::new(),
+            batch_server_limit: ServerRateLimit::new(),
+            rate_limit_handling: settings.rate_limit_handling,
+            retry_policy: settings.retry_policy,
+            reserved_range_check: settings.reserved_range_check.unwrap_or(true),
+            api_key: settings.api_key,
+            response_format: settings.response_format,
+            language: settings.language,
+            base_url: settings.base_url,
+            edns_base_url: settings.edns_base_url,
+            cache: settings.cache,
+        })
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl IpApiClientBuilder<BlockingIpApiClient> {
+    /// Sets a custom http client for the `BlockingIpApiClient`.
+    /// Timeouts and the user agent have to be configured on the custom client itself.
+    ///
+    /// # Arguments
+    /// * `client` - The blocking http client.
+    pub fn blocking_http_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.settings.blocking_http_client = Some(client);
+        self
+    }
+
+    /// Builds the `BlockingIpApiClient`.
+    ///
+    /// # Returns
+    /// * `Result<BlockingIpApiClient, IpApiError>` - The client or `IpApiError::InvalidConfiguration`.
+    pub fn build_blocking(self) -> Result<BlockingIpApiClient, IpApiError> {
+        let settings = self.settings;
+        settings.validate()?;
+        let client = match settings.blocking_http_client {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::blocking::Client::builder();
+                if let Some(timeout) = settings.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = settings.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(user_agent) = &settings.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                builder.build()?
+            }
+        };
+        Ok(BlockingIpApiClient {
+            client,
+            limiter: settings.quota.unwrap_or_else(|| default_quota(&settings.api_key)).map(RateLimiter::direct),
+            batch_limiter: settings
+                .batch_quota
+                .unwrap_or_else(|| default_batch_quota(&settings.api_key))
+                .map(RateLimiter::direct),
+            server_limit: ServerRateLimit::new(),
+            batch_server_limit: ServerRateLimit::new(),
+            rate_limit_handling: settings.rate_limit_handling,
+            retry_policy: settings.retry_policy,
+            reserved_range_check: settings.reserved_range_check.unwrap_or(true),
+            api_key: settings.api_key,
+            response_format: settings.response_format,
+            language: settings.language,
+            base_url: settings.base_url,
+            edns_base_url: settings.edns_base_url,
+            cache: settings.cache,
+        })
+    }
+}
+
+/// The settings collected by the builder.
+#[derive(Debug, Default)]
+struct Settings {
+    api_key: Option<String>,
+    quota: Option<Option<Quota>>,
+    batch_quota: Option<Option<Quota>>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    base_url: Option<String>,
+    edns_base_url: Option<String>,
+    https: bool,
+    response_format: ResponseFormat,
+    language: Option<Language>,
+    rate_limit_handling: RateLimitHandling,
+    retry_policy: RetryPolicy,
+    reserved_range_check: Option<bool>,
+    cache: Option<Box<dyn ResponseCache>>,
+    http_client: Option<Client>,
+    #[cfg(feature = "blocking")]
+    blocking_http_client: Option<reqwest::blocking::Client>,
+}
+
+impl Settings {
+    /// Validates the combination of the settings.
+    ///
+    /// # Returns
+    /// * `Result<(), IpApiError>` - `IpApiError::InvalidConfiguration` if the settings do not fit together.
+    fn validate(&self) -> Result<(), IpApiError> {
+        if let Some(api_key) = &self.api_key {
+            if api_key.is_empty()
+                || !api_key.chars().all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_')
+            {
+                return Err(invalid_configuration("The API key must be a non-empty alphanumeric string."));
+            }
+        }
+        if let Some(base_url) = &self.base_url {
+            if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+                return Err(invalid_configuration("The base url must start with http:// or https://."));
+            }
+        }
+        if let Some(edns_base_url) = &self.edns_base_url {
+            if !edns_base_url.starts_with("http://") && !edns_base_url.starts_with("https://") {
+                return Err(invalid_configuration("The EDNS base url must start with http:// or https://."));
+            }
+        }
+        if self.https {
+            match &self.base_url {
+                Some(base_url) if !base_url.starts_with("https://") => {
+                    return Err(invalid_configuration("HTTPS is required, but the base url does not use it."));
+                }
+                None if self.api_key.is_none() => {
+                    return Err(invalid_configuration("The free tier does not support HTTPS, an API key is required."));
+                }
+                _ => {}
+            }
+            if self.edns_base_url.as_ref().is_some_and(|edns_base_url| !edns_base_url.starts_with("https://")) {
+                return Err(invalid_configuration("HTTPS is required, but the EDNS base url does not use it."));
+            }
+        }
+        if self.retry_policy.max_attempts == 0 {
+            return Err(invalid_configuration("The retry policy needs at least one attempt."));
+        }
+        let has_custom_client = self.http_client.is_some();
+        #[cfg(feature = "blocking")]
+        let has_custom_client = has_custom_client || self.blocking_http_client.is_some();
+        if has_custom_client && (self.timeout.is_some() || self.connect_timeout.is_some() || self.user_agent.is_some())
+        {
+            return Err(invalid_configuration(
+                "Timeouts and the user agent can not be applied to a custom http client.",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Gets the default quota for single requests of a tier.
+fn default_quota(api_key: &Option<String>) -> Option<Quota> {
+    match api_key {
+        Some(_) => None,
+        None => Some(Quota::per_minute(nonzero!(45u32))),
+    }
+}
+
+/// Gets the default quota for batch requests of a tier.
+fn default_batch_quota(api_key: &Option<String>) -> Option<Quota> {
+    match api_key {
+        Some(_) => None,
+        None => Some(Quota::per_minute(nonzero!(15u32))),
+    }
+}
+
+/// Creates an `IpApiError::InvalidConfiguration` with the given message.
+fn invalid_configuration(message: &str) -> IpApiError {
+    IpApiError::InvalidConfiguration(message.to_string())
+}
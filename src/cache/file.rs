@@ -0,0 +1,126 @@
+//! A module that contains a cache that stores the responses on disk.
+//!
+//! The cache survives restarts of the process, which helps short-lived jobs and CLI invocations.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{CacheKey, ResponseCache};
+
+/// The counter that makes the names of temporary files unique within the process.
+static TEMPORARY_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A cache that stores every response as its own file in a directory.
+/// Entries expire after the time to live.
+#[derive(Debug, Clone)]
+pub struct FileCache {
+    directory: PathBuf,
+    ttl: Duration,
+}
+
+/// The content of a cache file.
+#[derive(Deserialize, Serialize, Debug)]
+struct FileCacheEntry {
+    /// The point in time at which the entry expires, in milliseconds since the unix epoch.
+    expires_at_millis: u64,
+    /// The raw response of the API.
+    body: String,
+}
+
+impl FileCache {
+    /// Creates a new FileCache.
+    /// The directory is created if it does not exist.
+    ///
+    /// # Arguments
+    /// * `directory` - The directory to store the responses in.
+    /// * `ttl` - How long an entry stays valid.
+    ///
+    /// # Returns
+    /// * `io::Result<FileCache>` - The cache or the error that occurred while creating the directory.
+    pub fn new(directory: impl Into<PathBuf>, ttl: Duration) -> io::Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        Ok(Self {
+            directory,
+            ttl,
+        })
+    }
+
+    /// Gets the directory the responses are stored in.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Removes every entry from the cache.
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - The error that occurred while removing the files, if any.
+    pub fn clear(&self) -> io::Result<()> {
+        for entry in fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "json") {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the path of the file for a key.
+    fn path(&self, key: &CacheKey) -> PathBuf {
+        self.directory.join(format!("{}.json", key.to_file_name()))
+    }
+
+    /// Writes an entry to its file.
+    /// The entry is written to a temporary file first, so readers never see a partially written file.
+    /// Every write uses its own temporary file, so concurrent writers of the same key do not interfere.
+    /// The temporary file is removed again if the write fails.
+    fn write(&self, key: &CacheKey, entry: &FileCacheEntry) -> io::Result<()> {
+        let path = self.path(key);
+        let counter = TEMPORARY_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let temporary_path = path.with_extension(format!("{}.{}.tmp", process::id(), counter));
+        let result =
+            fs::write(&temporary_path, serde_json::to_vec(entry)?).and_then(|_| fs::rename(&temporary_path, path));
+        if result.is_err() {
+            let _ = fs::remove_file(&temporary_path);
+        }
+        result
+    }
+}
+
+impl ResponseCache for FileCache {
+    fn get(&self, key: &CacheKey) -> Option<String> {
+        let path = self.path(key);
+        let content = fs::read(&path).ok()?;
+        match serde_json::from_slice::<FileCacheEntry>(&content) {
+            Ok(entry) if entry.expires_at_millis > unix_time_millis() => Some(entry.body),
+            _ => {
+                let _ = fs::remove_file(path);
+                None
+            }
+        }
+    }
+
+    fn insert(&self, key: CacheKey, body: String) {
+        let entry = FileCacheEntry {
+            expires_at_millis: unix_time_millis().saturating_add(duration_millis(self.ttl)),
+            body,
+        };
+        let _ = self.write(&key, &entry);
+    }
+}
+
+/// Gets the current time in milliseconds since the unix epoch.
+fn unix_time_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(duration_millis).unwrap_or_default()
+}
+
+/// Converts a duration into milliseconds, saturating at `u64::MAX`.
+fn duration_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,152 @@
+//! A module that contains an in-memory cache with a time to live and a maximum size.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::cache::{CacheKey, ResponseCache};
+
+/// An in-memory cache for responses of the API.
+/// Entries expire after the time to live.
+/// Once the cache is full, the least recently used entry is evicted.
+#[derive(Debug)]
+pub struct MemoryCache {
+    ttl: Duration,
+    max_entries: usize,
+    state: Mutex<MemoryCacheState>,
+}
+
+/// The entries of the cache and their order of use.
+#[derive(Debug, Default)]
+struct MemoryCacheState {
+    /// The cached entries.
+    entries: HashMap<CacheKey, MemoryCacheEntry>,
+    /// The keys of the entries, ordered by their last use.
+    usage: BTreeMap<u64, CacheKey>,
+    /// The counter that orders the uses of the entries.
+    clock: u64,
+}
+
+/// A cached response.
+#[derive(Debug)]
+struct MemoryCacheEntry {
+    /// The raw response of the API.
+    body: String,
+    /// The point in time at which the entry expires or `None` if the ttl is too long to ever expire.
+    expires_at: Option<Instant>,
+    /// The last use of the entry.
+    last_used: u64,
+}
+
+impl MemoryCache {
+    /// Creates a new MemoryCache.
+    ///
+    /// # Arguments
+    /// * `ttl` - How long an entry stays valid.
+    /// * `max_entries` - The maximum amount of entries.
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries,
+            state: Mutex::new(MemoryCacheState::default()),
+        }
+    }
+
+    /// Gets a cached response.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the response.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The raw response or `None` if it is not cached or expired.
+    pub fn get(&self, key: &CacheKey) -> Option<String> {
+        let mut state = self.state.lock().unwrap();
+        let (expires_at, last_used) = {
+            let entry = state.entries.get(key)?;
+            (entry.expires_at, entry.last_used)
+        };
+        if expires_at.is_some_and(|expires_at| expires_at <= Instant::now()) {
+            state.remove(key);
+            return None;
+        }
+        state.usage.remove(&last_used);
+        let tick = state.tick();
+        state.usage.insert(tick, key.clone());
+        let entry = state.entries.get_mut(key)?;
+        entry.last_used = tick;
+        Some(entry.body.clone())
+    }
+
+    /// Caches a response.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the response.
+    /// * `body` - The raw response of the API.
+    pub fn insert(&self, key: CacheKey, body: String) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.remove(&key);
+        while state.entries.len() >= self.max_entries {
+            match state.usage.pop_first() {
+                Some((_, evicted)) => {
+                    state.entries.remove(&evicted);
+                }
+                None => break,
+            }
+        }
+        let tick = state.tick();
+        state.usage.insert(tick, key.clone());
+        state.entries.insert(
+            key,
+            MemoryCacheEntry {
+                body,
+                expires_at: Instant::now().checked_add(self.ttl),
+                last_used: tick,
+            },
+        );
+    }
+
+    /// Gets the amount of cached entries, including expired ones that were not evicted yet.
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Checks whether the cache contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every entry from the cache.
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.entries.clear();
+        state.usage.clear();
+    }
+}
+
+impl ResponseCache for MemoryCache {
+    fn get(&self, key: &CacheKey) -> Option<String> {
+        MemoryCache::get(self, key)
+    }
+
+    fn insert(&self, key: CacheKey, body: String) {
+        MemoryCache::insert(self, key, body)
+    }
+}
+
+impl MemoryCacheState {
+    /// Advances the clock and returns the new tick.
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Removes an entry and its usage.
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.usage.remove(&entry.last_used);
+        }
+    }
+}
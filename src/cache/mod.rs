@@ -0,0 +1,94 @@
+//! A module that contains the caches for responses of the API.
+//!
+//! A cache hit answers a query without sending a request and without waiting for the rate limiter.
+//! Only successful responses are cached.
+
+use std::fmt::{Debug, Write};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::language::Language;
+use crate::util::urls::build_fields_param_from_struct;
+
+pub mod file;
+pub mod memory;
+
+/// A cache the clients consult before sending a request.
+/// Caches are best effort: a failing cache behaves like a cache miss.
+pub trait ResponseCache: Debug + Send + Sync {
+    /// Gets a cached response.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the response.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The raw response or `None` if it is not cached or expired.
+    fn get(&self, key: &CacheKey) -> Option<String>;
+
+    /// Caches a response.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the response.
+    /// * `body` - The raw response of the API.
+    fn insert(&self, key: CacheKey, body: String);
+}
+
+/// The key of a cached response.
+/// Responses are only shared between queries that ask for the same fields in the same language.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// The queried IP address or domain.
+    pub query: String,
+    /// The value of the `fields` parameter.
+    pub fields: String,
+    /// The language of the response, `None` is the default of the API.
+    pub language: Option<Language>,
+}
+
+impl CacheKey {
+    /// Creates a new CacheKey.
+    ///
+    /// # Arguments
+    /// * `query` - The queried IP address or domain.
+    /// * `fields` - The value of the `fields` parameter.
+    /// * `language` - The language of the response.
+    pub fn new(query: &str, fields: impl Into<String>, language: Option<Language>) -> Self {
+        Self {
+            query: query.to_string(),
+            fields: fields.into(),
+            language,
+        }
+    }
+
+    /// Creates a new CacheKey for a query with a custom struct.
+    /// The fields are automatically extracted from the struct.
+    ///
+    /// # Arguments
+    /// * `query` - The queried IP address or domain.
+    /// * `language` - The language of the response.
+    pub fn from_struct<'de, T>(query: &str, language: Option<Language>) -> Self
+    where
+        T: Deserialize<'de>,
+    {
+        Self {
+            query: query.to_string(),
+            fields: build_fields_param_from_struct::<T>(),
+            language,
+        }
+    }
+
+    /// Encodes the key into a string that is safe to use as file name.
+    /// The key is hashed, so the name has the same length for every query and fits the limits of file systems.
+    ///
+    /// # Returns
+    /// * `String` - The hex encoded SHA-256 hash of the key, 64 characters long.
+    pub fn to_file_name(&self) -> String {
+        let language = self.language.map(|language| language.code()).unwrap_or_default();
+        let hash = Sha256::digest(format!("{}|{}|{}", self.query, self.fields, language));
+        hash.iter().fold(String::new(), |mut name, byte| {
+            let _ = write!(name, "{:02x}", byte);
+            name
+        })
+    }
+}
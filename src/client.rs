@@ -5,7 +5,14 @@ use serde::de::DeserializeOwned;
 use std::future::Future;
 
 use crate::error::IpApiError;
-use crate::model::ip_response::{IpDefaultResponse, IpFullResponse};
+use crate::field::Field;
+use crate::format::ResponseFormat;
+use crate::language::Language;
+use crate::model::edns_response::EdnsResponse;
+use crate::model::ip_response::{IpDefaultResponse, IpFullResponse, IpResponse};
+use crate::query::IntoQuery;
+use crate::util::rate_limit::{RateLimitHandling, ServerRateLimit};
+use crate::util::retry::RetryPolicy;
 
 /// The main client for the ip-api.com API.
 pub trait IpApi {
@@ -15,11 +22,73 @@ pub trait IpApi {
     /// * `Option<String>` - The optional API key.
     fn get_api_key(&self) -> &Option<String>;
 
+    /// Gets the custom base url of the API.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The custom base url, `None` uses ip-api.com.
+    fn get_base_url(&self) -> &Option<String>;
+
+    /// Gets the custom base url of the EDNS endpoint.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The custom base url, `None` uses a random subdomain of edns.ip-api.com.
+    fn get_edns_base_url(&self) -> &Option<String>;
+
+    /// Gets the format the API answers single requests in.
+    /// Batch requests are always answered in JSON.
+    ///
+    /// # Returns
+    /// * `ResponseFormat` - The format of the responses.
+    fn get_response_format(&self) -> ResponseFormat;
+
+    /// Gets the default language of the responses.
+    ///
+    /// # Returns
+    /// * `Option<Language>` - The default language, `None` uses the default of the API.
+    fn get_language(&self) -> &Option<Language>;
+
     /// Gets the rate limiter.
     ///
     /// # Returns
     /// * `&DefaultDirectRateLimiter` - The rate limiter.
     fn get_rate_limiter(&self) -> &Option<DefaultDirectRateLimiter>;
+
+    /// Gets the rate limiter for batch requests.
+    /// The batch endpoint has its own quota, separate from the one for single requests.
+    ///
+    /// # Returns
+    /// * `&DefaultDirectRateLimiter` - The rate limiter for batch requests.
+    fn get_batch_rate_limiter(&self) -> &Option<DefaultDirectRateLimiter>;
+
+    /// Gets the rate limit the API reported through the `X-Rl` and `X-Ttl` headers.
+    ///
+    /// # Returns
+    /// * `&ServerRateLimit` - The rate limit reported by the API.
+    fn get_server_rate_limit(&self) -> &ServerRateLimit;
+
+    /// Gets the rate limit the API reported for batch requests.
+    ///
+    /// # Returns
+    /// * `&ServerRateLimit` - The rate limit reported by the API for batch requests.
+    fn get_batch_server_rate_limit(&self) -> &ServerRateLimit;
+
+    /// Gets how requests the API rejected because the rate limit is exceeded are handled.
+    ///
+    /// # Returns
+    /// * `RateLimitHandling` - The handling of an exceeded rate limit.
+    fn get_rate_limit_handling(&self) -> RateLimitHandling;
+
+    /// Gets the policy for retrying requests that failed for transient reasons.
+    ///
+    /// # Returns
+    /// * `&RetryPolicy` - The retry policy.
+    fn get_retry_policy(&self) -> &RetryPolicy;
+
+    /// Gets whether IP addresses in private and reserved ranges are rejected without sending a request.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the local range check is enabled.
+    fn get_reserved_range_check(&self) -> bool;
 }
 
 /// The blocking client for the ip-api.com API.
@@ -28,33 +97,120 @@ pub trait BlockingIpApi: IpApi {
     /// Queries the API with the default fields.
     ///
     /// # Arguments
-    /// * `ip` - The IP address to query.
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
     ///
     /// # Returns
     /// * `IpDefaultResponse` - The response from the API.
-    fn query_api_default(&self, ip: &str) -> Result<IpDefaultResponse, IpApiError>;
+    fn query_api_default(&self, ip: impl IntoQuery) -> Result<IpDefaultResponse, IpApiError>;
 
     /// Queries the API with all fields.
     ///
     /// # Arguments
-    /// * `ip` - The IP address to query.
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
     ///
     /// # Returns
     /// * `IpFullResponse` - The response from the API.
-    fn query_api_fully(&self, ip: &str) -> Result<IpFullResponse, IpApiError>;
+    fn query_api_fully(&self, ip: impl IntoQuery) -> Result<IpFullResponse, IpApiError>;
 
     /// Queries the API with a custom struct.
     ///
     /// # Arguments
-    /// * `ip` - The IP address to query.
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
+    /// * `T` - The custom struct to deserialize the response into.
+    ///
+    /// # Returns
+    /// * `T` - The response from the API.
+    fn query_api<T>(&self, ip: impl IntoQuery) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned;
+
+    /// Queries the API with a custom struct and a language that overrides the default of the client.
+    ///
+    /// # Arguments
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
+    /// * `language` - The language of the response.
     /// * `T` - The custom struct to deserialize the response into.
     ///
     /// # Returns
     /// * `T` - The response from the API.
-    fn query_api<T>(&self, ip: &str) -> Result<T, IpApiError>
+    fn query_api_localized<T>(&self, ip: impl IntoQuery, language: Language) -> Result<T, IpApiError>
     where
         T: DeserializeOwned;
 
+    /// Queries the API for fields that are chosen at runtime.
+    ///
+    /// # Arguments
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
+    /// * `fields` - The fields to request, none requests the default fields of the API.
+    ///
+    /// # Returns
+    /// * `IpResponse` - The response from the API.
+    fn query_fields(&self, ip: impl IntoQuery, fields: &[Field]) -> Result<IpResponse, IpApiError>;
+
+    /// Queries the API for the unparsed body of a response in the given format, like a CSV record.
+    /// Failed responses are returned as errors, like they are for the other methods.
+    ///
+    /// # Arguments
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
+    /// * `format` - The format of the response.
+    /// * `T` - The custom struct whose fields are requested.
+    ///
+    /// # Returns
+    /// * `String` - The body of the response.
+    fn query_api_raw<T>(&self, ip: impl IntoQuery, format: ResponseFormat) -> Result<String, IpApiError>
+    where
+        T: DeserializeOwned;
+
+    /// Queries the API for the public IP address of the caller.
+    /// The response is never cached, so a changed address is noticed right away.
+    ///
+    /// # Arguments
+    /// * `T` - The custom struct to deserialize the response into.
+    ///
+    /// # Returns
+    /// * `T` - The response from the API.
+    fn query_self<T>(&self) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned;
+
+    /// Queries the EDNS endpoint for the DNS resolver the own lookups go through.
+    /// A resolver or client subnet outside the expected network, like a VPN tunnel, reveals a DNS leak.
+    /// The EDNS endpoint of ip-api.com is only available over HTTP.
+    ///
+    /// # Returns
+    /// * `EdnsResponse` - The resolver and the EDNS client subnet seen by the API.
+    fn query_edns(&self) -> Result<EdnsResponse, IpApiError>;
+
+    /// Queries the API for multiple IP addresses in a single batch request.
+    /// Every query gets its own result, so a single failing query does not fail the whole batch.
+    ///
+    /// # Arguments
+    /// * `queries` - The IP addresses or hostnames to query, at most 100.
+    /// * `T` - The custom struct to deserialize the responses into.
+    ///
+    /// # Returns
+    /// * `Vec<Result<T, IpApiError>>` - The responses from the API, in the order of the queries.
+    fn query_api_batch<T>(&self, queries: &[impl IntoQuery + Clone]) -> Result<Vec<Result<T, IpApiError>>, IpApiError>
+    where
+        T: DeserializeOwned;
+
+    /// Queries the API for any amount of IP addresses.
+    /// The queries are split into batches of 100, which are sent one after another under the batch quota.
+    /// A batch whose request fails, like after the retries for a server error ran out,
+    /// fails its queries with `IpApiError::BatchFailed`, the results of the other batches are kept.
+    ///
+    /// # Arguments
+    /// * `queries` - The IP addresses or hostnames to query.
+    /// * `T` - The custom struct to deserialize the responses into.
+    ///
+    /// # Returns
+    /// * `Vec<Result<T, IpApiError>>` - The responses from the API, in the order of the queries.
+    fn query_api_bulk<T, I>(&self, queries: I) -> Result<Vec<Result<T, IpApiError>>, IpApiError>
+    where
+        T: DeserializeOwned,
+        I: IntoIterator,
+        I::Item: IntoQuery;
+
     /// Gets you the blocking http client.
     ///
     /// # Returns
@@ -67,32 +223,144 @@ pub trait AsyncIpApi: IpApi {
     /// Queries the API with the default fields.
     ///
     /// # Arguments
-    /// * `ip` - The IP address to query.
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
     ///
     /// # Returns
     /// * `IpDefaultResponse` - The response from the API.
-    fn query_api_default(&self, ip: &str) -> impl Future<Output = Result<IpDefaultResponse, IpApiError>> + Send;
+    fn query_api_default(
+        &self,
+        ip: impl IntoQuery + Send,
+    ) -> impl Future<Output = Result<IpDefaultResponse, IpApiError>> + Send;
 
     /// Queries the API with all fields.
     ///
     /// # Arguments
-    /// * `ip` - The IP address to query.
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
     ///
     /// # Returns
     /// * `IpFullResponse` - The response from the API.
-    fn query_api_fully(&self, ip: &str) -> impl Future<Output = Result<IpFullResponse, IpApiError>> + Send;
+    fn query_api_fully(
+        &self,
+        ip: impl IntoQuery + Send,
+    ) -> impl Future<Output = Result<IpFullResponse, IpApiError>> + Send;
 
     /// Queries the API with a custom struct.
     ///
     /// # Arguments
-    /// * `ip` - The IP address to query.
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
     /// * `T` - The custom struct to deserialize the response into.
     ///
     /// # Returns
     /// * `T` - The response from the API.
-    fn query_api<T>(&self, ip: &str) -> impl Future<Output = Result<T, IpApiError>> + Send
+    fn query_api<T>(&self, ip: impl IntoQuery + Send) -> impl Future<Output = Result<T, IpApiError>> + Send
     where
         T: DeserializeOwned;
+
+    /// Queries the API with a custom struct and a language that overrides the default of the client.
+    ///
+    /// # Arguments
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
+    /// * `language` - The language of the response.
+    /// * `T` - The custom struct to deserialize the response into.
+    ///
+    /// # Returns
+    /// * `T` - The response from the API.
+    fn query_api_localized<T>(
+        &self,
+        ip: impl IntoQuery + Send,
+        language: Language,
+    ) -> impl Future<Output = Result<T, IpApiError>> + Send
+    where
+        T: DeserializeOwned;
+
+    /// Queries the API for fields that are chosen at runtime.
+    ///
+    /// # Arguments
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
+    /// * `fields` - The fields to request, none requests the default fields of the API.
+    ///
+    /// # Returns
+    /// * `IpResponse` - The response from the API.
+    fn query_fields(
+        &self,
+        ip: impl IntoQuery + Send,
+        fields: &[Field],
+    ) -> impl Future<Output = Result<IpResponse, IpApiError>> + Send;
+
+    /// Queries the API for the unparsed body of a response in the given format, like a CSV record.
+    /// Failed responses are returned as errors, like they are for the other methods.
+    ///
+    /// # Arguments
+    /// * `ip` - The IP address or hostname to query, an empty string looks up the own IP address.
+    /// * `format` - The format of the response.
+    /// * `T` - The custom struct whose fields are requested.
+    ///
+    /// # Returns
+    /// * `String` - The body of the response.
+    fn query_api_raw<T>(
+        &self,
+        ip: impl IntoQuery + Send,
+        format: ResponseFormat,
+    ) -> impl Future<Output = Result<String, IpApiError>> + Send
+    where
+        T: DeserializeOwned;
+
+    /// Queries the API for the public IP address of the caller.
+    /// The response is never cached, so a changed address is noticed right away.
+    ///
+    /// # Arguments
+    /// * `T` - The custom struct to deserialize the response into.
+    ///
+    /// # Returns
+    /// * `T` - The response from the API.
+    fn query_self<T>(&self) -> impl Future<Output = Result<T, IpApiError>> + Send
+    where
+        T: DeserializeOwned;
+
+    /// Queries the EDNS endpoint for the DNS resolver the own lookups go through.
+    /// A resolver or client subnet outside the expected network, like a VPN tunnel, reveals a DNS leak.
+    /// The EDNS endpoint of ip-api.com is only available over HTTP.
+    ///
+    /// # Returns
+    /// * `EdnsResponse` - The resolver and the EDNS client subnet seen by the API.
+    fn query_edns(&self) -> impl Future<Output = Result<EdnsResponse, IpApiError>> + Send;
+
+    /// Queries the API for multiple IP addresses in a single batch request.
+    /// Every query gets its own result, so a single failing query does not fail the whole batch.
+    ///
+    /// # Arguments
+    /// * `queries` - The IP addresses or hostnames to query, at most 100.
+    /// * `T` - The custom struct to deserialize the responses into.
+    ///
+    /// # Returns
+    /// * `Vec<Result<T, IpApiError>>` - The responses from the API, in the order of the queries.
+    fn query_api_batch<T>(
+        &self,
+        queries: &[impl IntoQuery + Clone + Sync],
+    ) -> impl Future<Output = Result<Vec<Result<T, IpApiError>>, IpApiError>> + Send
+    where
+        T: DeserializeOwned;
+
+    /// Queries the API for any amount of IP addresses.
+    /// The queries are split into batches of 100, which are sent one after another under the batch quota.
+    /// A batch whose request fails, like after the retries for a server error ran out,
+    /// fails its queries with `IpApiError::BatchFailed`, the results of the other batches are kept.
+    ///
+    /// # Arguments
+    /// * `queries` - The IP addresses or hostnames to query.
+    /// * `T` - The custom struct to deserialize the responses into.
+    ///
+    /// # Returns
+    /// * `Vec<Result<T, IpApiError>>` - The responses from the API, in the order of the queries.
+    fn query_api_bulk<T, I>(
+        &self,
+        queries: I,
+    ) -> impl Future<Output = Result<Vec<Result<T, IpApiError>>, IpApiError>> + Send
+    where
+        T: DeserializeOwned + Send,
+        I: IntoIterator,
+        I::Item: IntoQuery;
+
     /// Gets you the async http client.
     ///
     /// # Returns
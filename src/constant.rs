@@ -2,14 +2,66 @@
 //!
 //! This module defines the constants used in the library
 
+use std::time::Duration;
+
+/// Defines the http base url of the ip-api.com service
+pub const HTTP_BASE_URL: &str = "http://ip-api.com";
+
+/// Defines the base url of the pro tier of the ip-api.com service
+pub const PRO_BASE_URL: &str = "https://pro.ip-api.com";
+
 /// Defines the http host for the ip-api.com service
 pub const HTTP_HOST: &str = "http://ip-api.com/json/";
 
 /// Defines the https host for the ip-api.com service
 pub const HTTPS_HOST: &str = "https://ip-api.com/json/";
 
+/// Defines the host for the pro tier of the ip-api.com service
+/// The pro tier is only available over https and requires an API key.
+pub const PRO_HOST: &str = "https://pro.ip-api.com/json/";
+
+/// Defines the http host for the batch endpoint of the ip-api.com service
+pub const HTTP_BATCH_HOST: &str = "http://ip-api.com/batch";
+
+/// Defines the https host for the batch endpoint of the ip-api.com service
+pub const HTTPS_BATCH_HOST: &str = "https://ip-api.com/batch";
+
+/// Defines the host for the batch endpoint of the pro tier of the ip-api.com service
+pub const PRO_BATCH_HOST: &str = "https://pro.ip-api.com/batch";
+
+/// Defines the domain of the EDNS endpoint of the ip-api.com service.
+/// Every request goes to a random subdomain, so the resolver can not answer it from its cache.
+pub const EDNS_DOMAIN: &str = "edns.ip-api.com";
+
+/// The maximum amount of queries the batch endpoint accepts in a single request.
+pub const BATCH_SIZE_LIMIT: usize = 100;
+
+/// The header in which the API reports the amount of requests left in the current window.
+pub const HEADER_REQUESTS_LEFT: &str = "X-Rl";
+
+/// The header in which the API reports the seconds until the current window resets.
+pub const HEADER_TIME_TO_RESET: &str = "X-Ttl";
+
+/// The time to wait after an exceeded rate limit, if the API did not report the time to reset.
+pub const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// The maximum amount of times a request waits for the rate limit to reset, before the exceeded rate limit is returned.
+pub const MAX_RATE_LIMIT_WAITS: u32 = 3;
+
 /// The string to check the message against when an error occurred.
 pub const ERROR_RESERVED_RANGE: &str = "reserved range";
 
 /// The string to check the message against when an invalid query occurred.
 pub const ERROR_INVALID_QUERY: &str = "invalid query";
+
+/// The string to check the message against when the query is in a private range.
+pub const ERROR_PRIVATE_RANGE: &str = "private range";
+
+/// The string to check the message against when the API key is invalid.
+pub const ERROR_INVALID_KEY: &str = "invalid key";
+
+/// The start of the message when HTTPS is used without an API key.
+pub const ERROR_SSL_UNAVAILABLE: &str = "SSL unavailable";
+
+/// The string to check the message against when the quota of the API key is used up.
+pub const ERROR_QUOTA_EXCEEDED: &str = "quota exceeded";
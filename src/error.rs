@@ -1,6 +1,9 @@
 //! A module that contains the error type for the library.
 
+use crate::format::ResponseFormat;
 use crate::model::ip_response::ErrorResponse;
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 /// An enum that contains all the possible errors that can occur when querying the API.
@@ -8,12 +11,94 @@ use thiserror::Error;
 pub enum IpApiError {
     #[error("The supplied ip address is in a reserved range.")]
     ReservedRange(ErrorResponse),
+    #[error("The supplied ip address is in a private range.")]
+    PrivateRange(ErrorResponse),
     #[error("The supplied ip address is invalid.")]
     InvalidQuery(ErrorResponse),
+    #[error("The API key is invalid.")]
+    InvalidKey(ErrorResponse),
+    #[error("HTTPS is not available without an API key.")]
+    SslUnavailable(ErrorResponse),
+    #[error("The quota of the API key has been exceeded.")]
+    QuotaExceeded(ErrorResponse),
+    #[error("A batch request may contain at most 100 queries.")]
+    BatchTooLarge(usize),
+    #[error("The batch request of the query failed.")]
+    BatchFailed {
+        query: String,
+        #[source]
+        source: Arc<IpApiError>,
+    },
+    #[error("The rate limit of the API has been exceeded.")]
+    RateLimited {
+        retry_after: Duration,
+    },
+    #[error("The API responded with HTTP status {status}.")]
+    HttpStatus {
+        status: u16,
+        query: Option<String>,
+    },
+    #[error("The API responded with a body that is not JSON.")]
+    NonJsonBody {
+        body: String,
+        query: Option<String>,
+    },
+    #[error("The API responded with a body that is not valid {format}.")]
+    MalformedBody {
+        format: ResponseFormat,
+        body: String,
+        query: Option<String>,
+    },
     #[error("An error occurred while parsing the JSON.")]
     JsonParseError(#[from] serde_json::Error),
     #[error("An error occurred while querying the API.")]
     ReqwestError(#[from] reqwest::Error),
+    #[error("The client configuration is invalid.")]
+    InvalidConfiguration(String),
     #[error("An unknown error occurred.")]
     Unknown(String),
 }
+
+impl IpApiError {
+    /// Gets the failed response of the API, if the error has one.
+    ///
+    /// # Returns
+    /// * `Option<&ErrorResponse>` - The failed response.
+    pub fn error_response(&self) -> Option<&ErrorResponse> {
+        match self {
+            IpApiError::ReservedRange(response)
+            | IpApiError::PrivateRange(response)
+            | IpApiError::InvalidQuery(response)
+            | IpApiError::InvalidKey(response)
+            | IpApiError::SslUnavailable(response)
+            | IpApiError::QuotaExceeded(response) => Some(response),
+            _ => None,
+        }
+    }
+
+    /// Gets the query that caused the error, if it is known.
+    ///
+    /// # Returns
+    /// * `Option<&str>` - The query.
+    pub fn query(&self) -> Option<&str> {
+        match self {
+            IpApiError::HttpStatus {
+                query,
+                ..
+            }
+            | IpApiError::NonJsonBody {
+                query,
+                ..
+            }
+            | IpApiError::MalformedBody {
+                query,
+                ..
+            } => query.as_deref(),
+            IpApiError::BatchFailed {
+                query,
+                ..
+            } => Some(query),
+            _ => self.error_response().map(|response| response.query.as_str()),
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! A module that contains the fields the API can return.
+//!
+//! The API accepts the requested fields either as a comma separated list of names or as a numeric bitmask.
+//! The bitmask keeps the urls short, which matters for batch requests.
+
+use std::fmt::{Display, Formatter};
+use std::ops::{BitOr, BitOrAssign};
+
+/// A field the API can return, in the order the API documents them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Field {
+    /// `success` or `fail`.
+    Status,
+    /// The error message, only included when the status is `fail`.
+    Message,
+    /// The continent name.
+    Continent,
+    /// The two-letter continent code.
+    ContinentCode,
+    /// The country name.
+    Country,
+    /// The two-letter country code (ISO 3166-1 alpha-2).
+    CountryCode,
+    /// The region or state short code (FIPS or ISO).
+    Region,
+    /// The region or state name.
+    RegionName,
+    /// The city name.
+    City,
+    /// The district (subdivision of the city).
+    District,
+    /// The zip code.
+    Zip,
+    /// The latitude.
+    Lat,
+    /// The longitude.
+    Lon,
+    /// The timezone (tz).
+    Timezone,
+    /// The timezone UTC DST offset in seconds.
+    Offset,
+    /// The national currency.
+    Currency,
+    /// The ISP name.
+    Isp,
+    /// The organization name.
+    Org,
+    /// The AS number and organization, separated by a space (RIR).
+    As,
+    /// The AS name (RIR).
+    AsName,
+    /// The reverse DNS of the IP.
+    Reverse,
+    /// Whether the IP belongs to a mobile (cellular) connection.
+    Mobile,
+    /// Whether the IP belongs to a proxy, VPN or Tor exit address.
+    Proxy,
+    /// Whether the IP belongs to a hosting, colocated or data center.
+    Hosting,
+    /// The IP used for the query.
+    Query,
+}
+
+impl Field {
+    /// Every field, in the order the API documents them.
+    pub const ALL: [Field; 25] = [
+        Field::Status,
+        Field::Message,
+        Field::Continent,
+        Field::ContinentCode,
+        Field::Country,
+        Field::CountryCode,
+        Field::Region,
+        Field::RegionName,
+        Field::City,
+        Field::District,
+        Field::Zip,
+        Field::Lat,
+        Field::Lon,
+        Field::Timezone,
+        Field::Offset,
+        Field::Currency,
+        Field::Isp,
+        Field::Org,
+        Field::As,
+        Field::AsName,
+        Field::Reverse,
+        Field::Mobile,
+        Field::Proxy,
+        Field::Hosting,
+        Field::Query,
+    ];
+
+    /// The fields the API returns if no fields are requested, in the order the API documents them.
+    pub const DEFAULT: [Field; 14] = [
+        Field::Status,
+        Field::Country,
+        Field::CountryCode,
+        Field::Region,
+        Field::RegionName,
+        Field::City,
+        Field::Zip,
+        Field::Lat,
+        Field::Lon,
+        Field::Timezone,
+        Field::Isp,
+        Field::Org,
+        Field::As,
+        Field::Query,
+    ];
+
+    /// Gets the name of the field, as used in the responses and the `fields` parameter.
+    ///
+    /// # Returns
+    /// * `&'static str` - The name of the field.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Field::Status => "status",
+            Field::Message => "message",
+            Field::Continent => "continent",
+            Field::ContinentCode => "continentCode",
+            Field::Country => "country",
+            Field::CountryCode => "countryCode",
+            Field::Region => "region",
+            Field::RegionName => "regionName",
+            Field::City => "city",
+            Field::District => "district",
+            Field::Zip => "zip",
+            Field::Lat => "lat",
+            Field::Lon => "lon",
+            Field::Timezone => "timezone",
+            Field::Offset => "offset",
+            Field::Currency => "currency",
+            Field::Isp => "isp",
+            Field::Org => "org",
+            Field::As => "as",
+            Field::AsName => "asname",
+            Field::Reverse => "reverse",
+            Field::Mobile => "mobile",
+            Field::Proxy => "proxy",
+            Field::Hosting => "hosting",
+            Field::Query => "query",
+        }
+    }
+
+    /// Gets the bit of the field in the numeric `fields` parameter.
+    ///
+    /// # Returns
+    /// * `u32` - The bit of the field.
+    pub const fn bit(&self) -> u32 {
+        match self {
+            Field::Status => 1 << 14,
+            Field::Message => 1 << 15,
+            Field::Continent => 1 << 20,
+            Field::ContinentCode => 1 << 21,
+            Field::Country => 1 << 0,
+            Field::CountryCode => 1 << 1,
+            Field::Region => 1 << 2,
+            Field::RegionName => 1 << 3,
+            Field::City => 1 << 4,
+            Field::District => 1 << 19,
+            Field::Zip => 1 << 5,
+            Field::Lat => 1 << 6,
+            Field::Lon => 1 << 7,
+            Field::Timezone => 1 << 8,
+            Field::Offset => 1 << 25,
+            Field::Currency => 1 << 23,
+            Field::Isp => 1 << 9,
+            Field::Org => 1 << 10,
+            Field::As => 1 << 11,
+            Field::AsName => 1 << 22,
+            Field::Reverse => 1 << 12,
+            Field::Mobile => 1 << 16,
+            Field::Proxy => 1 << 17,
+            Field::Hosting => 1 << 24,
+            Field::Query => 1 << 13,
+        }
+    }
+
+    /// Gets the field with the given name.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the field, as used in the responses.
+    ///
+    /// # Returns
+    /// * `Option<Field>` - The field or `None` if the API has no field with that name.
+    pub fn from_name(name: &str) -> Option<Field> {
+        Field::ALL.into_iter().find(|field| field.name() == name)
+    }
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A set of fields that encodes to the numeric `fields` parameter of the API.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldSet(u32);
+
+impl FieldSet {
+    /// Creates an empty FieldSet.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a FieldSet that contains every field.
+    pub const fn all() -> Self {
+        let mut bits = 0;
+        let mut index = 0;
+        while index < Field::ALL.len() {
+            bits |= Field::ALL[index].bit();
+            index += 1;
+        }
+        Self(bits)
+    }
+
+    /// Creates a FieldSet from its numeric representation.
+    /// Bits that do not belong to a field are dropped.
+    ///
+    /// # Arguments
+    /// * `bits` - The numeric representation.
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits & Self::all().0)
+    }
+
+    /// Creates a FieldSet from the names of the fields.
+    ///
+    /// # Arguments
+    /// * `names` - The names of the fields, as used in the responses.
+    ///
+    /// # Returns
+    /// * `Option<FieldSet>` - The FieldSet or `None` if any of the names is not a field of the API.
+    pub fn from_names(names: &[&str]) -> Option<Self> {
+        names.iter().map(|name| Field::from_name(name)).collect()
+    }
+
+    /// Creates a FieldSet from a list of fields, an empty list becomes the default fields of the API.
+    ///
+    /// # Arguments
+    /// * `fields` - The fields.
+    pub fn from_fields_or_default(fields: &[Field]) -> Self {
+        match fields.is_empty() {
+            true => Field::DEFAULT.into_iter().collect(),
+            false => fields.iter().copied().collect(),
+        }
+    }
+
+    /// Gets the numeric representation, as expected by the `fields` parameter.
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Adds a field to the set.
+    ///
+    /// # Arguments
+    /// * `field` - The field to add.
+    ///
+    /// # Returns
+    /// * `FieldSet` - The set with the field added.
+    pub const fn with(self, field: Field) -> Self {
+        Self(self.0 | field.bit())
+    }
+
+    /// Checks whether the set contains a field.
+    pub const fn contains(&self, field: Field) -> bool {
+        self.0 & field.bit() != 0
+    }
+
+    /// Checks whether the set contains no fields.
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Gets the fields of the set, in the order the API documents them.
+    ///
+    /// # Returns
+    /// * `Vec<Field>` - The fields of the set.
+    pub fn fields(&self) -> Vec<Field> {
+        Field::ALL.into_iter().filter(|field| self.contains(*field)).collect()
+    }
+}
+
+impl From<Field> for FieldSet {
+    fn from(field: Field) -> Self {
+        Self(field.bit())
+    }
+}
+
+impl FromIterator<Field> for FieldSet {
+    fn from_iter<I: IntoIterator<Item = Field>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::empty(), FieldSet::with)
+    }
+}
+
+impl BitOr for FieldSet {
+    type Output = FieldSet;
+
+    fn bitor(self, rhs: FieldSet) -> FieldSet {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOr<Field> for FieldSet {
+    type Output = FieldSet;
+
+    fn bitor(self, rhs: Field) -> FieldSet {
+        self.with(rhs)
+    }
+}
+
+impl BitOr for Field {
+    type Output = FieldSet;
+
+    fn bitor(self, rhs: Field) -> FieldSet {
+        FieldSet::from(self).with(rhs)
+    }
+}
+
+impl BitOrAssign<Field> for FieldSet {
+    fn bitor_assign(&mut self, rhs: Field) {
+        *self = self.with(rhs);
+    }
+}
+
+impl Display for FieldSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
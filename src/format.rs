@@ -0,0 +1,287 @@
+//! A module that contains the formats the API can respond in.
+//!
+//! Besides JSON the API serves XML, CSV and a line format with one value per line.
+//! CSV and line responses only contain the values, in the order the API documents the fields,
+//! so the columns are taken from the fields of the requested struct.
+//! Responses in these formats are decoded into JSON, which is then parsed into the same models as a JSON response.
+
+use std::fmt::{Display, Formatter};
+
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+
+use crate::error::IpApiError;
+use crate::field::{Field, FieldSet};
+use crate::util::introspect::struct_fields;
+
+/// The formats the API can respond in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResponseFormat {
+    /// JSON (`/json`), the default.
+    #[default]
+    Json,
+    /// XML (`/xml`), with one element per field.
+    Xml,
+    /// CSV (`/csv`), with the values of a single record.
+    Csv,
+    /// The line format (`/line`), with one value per line.
+    Line,
+}
+
+impl ResponseFormat {
+    /// Every format the API can respond in.
+    pub const ALL: [ResponseFormat; 4] =
+        [ResponseFormat::Json, ResponseFormat::Xml, ResponseFormat::Csv, ResponseFormat::Line];
+
+    /// Gets the path segment of the format, as used in the url of a single request.
+    ///
+    /// # Returns
+    /// * `&'static str` - The path segment of the format.
+    pub fn path(&self) -> &'static str {
+        match self {
+            ResponseFormat::Json => "json",
+            ResponseFormat::Xml => "xml",
+            ResponseFormat::Csv => "csv",
+            ResponseFormat::Line => "line",
+        }
+    }
+
+    /// Gets the media type of the format, as sent in the `Accept` header.
+    ///
+    /// # Returns
+    /// * `&'static str` - The media type of the format.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            ResponseFormat::Json => "application/json",
+            ResponseFormat::Xml => "application/xml",
+            ResponseFormat::Csv => "text/csv",
+            ResponseFormat::Line => "text/plain",
+        }
+    }
+
+    /// Gets a format from its path segment.
+    ///
+    /// # Arguments
+    /// * `path` - The path segment, like `csv`.
+    ///
+    /// # Returns
+    /// * `Option<ResponseFormat>` - The format or `None` if the API has no format with that path.
+    pub fn from_path(path: &str) -> Option<ResponseFormat> {
+        ResponseFormat::ALL.into_iter().find(|format| format.path() == path)
+    }
+}
+
+impl Display for ResponseFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.path())
+    }
+}
+
+/// Gets the columns of a CSV or line response for a struct.
+/// The API returns the requested fields in the order it documents them, names it does not know are skipped.
+///
+/// # Returns
+/// * `Vec<Field>` - The columns, the default fields of the API if the struct requests none.
+pub fn columns<'de, T>() -> Vec<Field>
+where
+    T: Deserialize<'de>,
+{
+    let fields: FieldSet = struct_fields::<T>().iter().filter_map(|name| Field::from_name(name)).collect();
+    match fields.is_empty() {
+        true => Field::DEFAULT.to_vec(),
+        false => fields.fields(),
+    }
+}
+
+/// Decodes a response into JSON.
+/// A failed response, which starts with `fail` in the CSV and the line format, is decoded into a failed JSON response.
+///
+/// # Arguments
+/// * `format` - The format of the response.
+/// * `body` - The body of the response.
+/// * `query` - The query of the request, empty for the own IP address.
+/// * `T` - The struct the response is parsed into, which defines the columns.
+///
+/// # Returns
+/// * `Result<String, ip-api4rs::error::IpApiError>` - The JSON or `IpApiError::MalformedBody`.
+pub fn decode<'de, T>(format: ResponseFormat, body: String, query: &str) -> Result<String, IpApiError>
+where
+    T: Deserialize<'de>,
+{
+    decode_columns(format, body, query, &columns::<T>())
+}
+
+/// Decodes a response with the given columns into JSON.
+///
+/// # Arguments
+/// * `format` - The format of the response.
+/// * `body` - The body of the response.
+/// * `query` - The query of the request, empty for the own IP address.
+/// * `columns` - The requested fields, in the order the API documents them.
+///
+/// # Returns
+/// * `Result<String, ip-api4rs::error::IpApiError>` - The JSON or `IpApiError::MalformedBody`.
+pub fn decode_columns(
+    format: ResponseFormat,
+    body: String,
+    query: &str,
+    columns: &[Field],
+) -> Result<String, IpApiError> {
+    let object = match format {
+        ResponseFormat::Json => return Ok(body),
+        ResponseFormat::Xml => decode_xml(&body),
+        ResponseFormat::Csv => split_csv(&body).and_then(|values| decode_values(values, columns)),
+        ResponseFormat::Line => decode_values(body.lines().map(str::to_string).collect(), columns),
+    };
+    match object {
+        Some(object) => Ok(Value::Object(object).to_string()),
+        None => Err(IpApiError::MalformedBody {
+            format,
+            body,
+            query: Some(query.to_string()).filter(|query| !query.is_empty()),
+        }),
+    }
+}
+
+/// Encodes a JSON object the way the API does in the given format.
+/// The fields are written in the order the API documents them, names it does not know last.
+///
+/// # Arguments
+/// * `format` - The format to encode in.
+/// * `object` - The JSON object of the response.
+///
+/// # Returns
+/// * `String` - The encoded response.
+pub fn encode(format: ResponseFormat, object: &Map<String, Value>) -> String {
+    let mut entries: Vec<(&String, &Value)> = object.iter().collect();
+    entries.sort_by_key(|(name, _)| Field::from_name(name).map_or(Field::ALL.len(), |field| field as usize));
+    match format {
+        ResponseFormat::Json => Value::Object(object.clone()).to_string(),
+        ResponseFormat::Xml => {
+            let elements: String = entries
+                .iter()
+                .map(|(name, value)| match value {
+                    Value::String(text) => format!("  <{}><![CDATA[{}]]></{}>\n", name, text, name),
+                    _ => format!("  <{}>{}</{}>\n", name, value_text(value), name),
+                })
+                .collect();
+            format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<query>\n{}</query>\n", elements)
+        }
+        ResponseFormat::Csv => {
+            entries.iter().map(|(_, value)| quote_csv(&value_text(value))).collect::<Vec<_>>().join(",")
+        }
+        ResponseFormat::Line => entries.iter().map(|(_, value)| value_text(value)).collect::<Vec<_>>().join("\n"),
+    }
+}
+
+/// Decodes the values of a CSV or line response into a JSON object.
+fn decode_values(values: Vec<String>, columns: &[Field]) -> Option<Map<String, Value>> {
+    if values.first().map(String::as_str) == Some("fail") {
+        let fail = [Field::Status, Field::Message, Field::Query];
+        return Some(
+            fail.iter().zip(values).map(|(field, value)| (field.name().to_string(), Value::from(value))).collect(),
+        );
+    }
+    // The `message` field is always requested, but the API leaves its column out of successful responses.
+    let with_message = columns.iter().copied().collect::<FieldSet>().with(Field::Message).fields();
+    let without_message: Vec<Field> = with_message.iter().copied().filter(|field| *field != Field::Message).collect();
+    let columns = match values.len() {
+        len if len == with_message.len() => &with_message,
+        len if len == without_message.len() => &without_message,
+        _ => return None,
+    };
+    Some(
+        columns
+            .iter()
+            .zip(values)
+            .map(|(field, value)| (field.name().to_string(), parse_value(field.name(), &value)))
+            .collect(),
+    )
+}
+
+/// Decodes the elements of an XML response into a JSON object.
+fn decode_xml(body: &str) -> Option<Map<String, Value>> {
+    let start = body.find("<query>")? + "<query>".len();
+    let end = body.rfind("</query>")?;
+    let mut rest = body.get(start..end)?;
+    let mut object = Map::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Some(object);
+        }
+        let close = rest.strip_prefix('<')?.find('>')? + 1;
+        let tag = &rest[1..close];
+        if let Some(name) = tag.strip_suffix('/') {
+            object.insert(name.trim().to_string(), Value::from(""));
+            rest = &rest[close + 1..];
+            continue;
+        }
+        let end_tag = format!("</{}>", tag);
+        let content_end = close + 1 + rest[close + 1..].find(&end_tag)?;
+        let content = &rest[close + 1..content_end];
+        let text = match content.strip_prefix("<![CDATA[").and_then(|content| content.strip_suffix("]]>")) {
+            Some(text) => text.to_string(),
+            None => decode_entities(content),
+        };
+        object.insert(tag.to_string(), parse_value(tag, &text));
+        rest = &rest[content_end + end_tag.len()..];
+    }
+}
+
+/// Splits a CSV record into its values.
+fn split_csv(body: &str) -> Option<Vec<String>> {
+    let mut values = Vec::new();
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut chars = body.trim_end_matches(['\r', '\n']).chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                value.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => values.push(std::mem::take(&mut value)),
+            _ => value.push(char),
+        }
+    }
+    values.push(value);
+    match quoted {
+        true => None,
+        false => Some(values),
+    }
+}
+
+/// Parses the text of a field into the JSON type the API uses for it.
+fn parse_value(name: &str, text: &str) -> Value {
+    let value = match Field::from_name(name) {
+        Some(Field::Lat | Field::Lon) => text.parse::<f64>().ok().and_then(Number::from_f64).map(Value::Number),
+        Some(Field::Offset) => text.parse::<i64>().ok().map(Value::from),
+        Some(Field::Mobile | Field::Proxy | Field::Hosting) => text.parse::<bool>().ok().map(Value::from),
+        _ => None,
+    };
+    value.unwrap_or_else(|| Value::from(text))
+}
+
+/// Decodes the predefined entities of XML.
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&apos;", "'").replace("&amp;", "&")
+}
+
+/// Gets the text of a JSON value, without the quotes of a string.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        _ => value.to_string(),
+    }
+}
+
+/// Quotes a CSV value, if it contains a separator, a quote, a space or a line break.
+fn quote_csv(value: &str) -> String {
+    match value.contains([',', '"', ' ', '\n']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
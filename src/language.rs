@@ -0,0 +1,50 @@
+//! A module that contains the languages the API can localize responses in.
+
+use std::fmt::{Display, Formatter};
+
+/// The languages the API can return the names of countries, regions and cities in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// English (`en`), the default of the API.
+    #[default]
+    English,
+    /// Deutsch (`de`).
+    German,
+    /// Español (`es`).
+    Spanish,
+    /// Português - Brasil (`pt-BR`).
+    PortugueseBrazil,
+    /// Français (`fr`).
+    French,
+    /// 日本語 (`ja`).
+    Japanese,
+    /// 中国 (`zh-CN`).
+    ChineseSimplified,
+    /// Русский (`ru`).
+    Russian,
+}
+
+impl Language {
+    /// Gets the code of the language, as expected by the `lang` parameter.
+    ///
+    /// # Returns
+    /// * `&'static str` - The code of the language.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::Spanish => "es",
+            Language::PortugueseBrazil => "pt-BR",
+            Language::French => "fr",
+            Language::Japanese => "ja",
+            Language::ChineseSimplified => "zh-CN",
+            Language::Russian => "ru",
+        }
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
@@ -5,20 +5,41 @@
 use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
 use nonzero_ext::nonzero;
 use reqwest::Client;
-use serde::de::DeserializeOwned;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use std::future::Future;
 
-use model::ip_response::{IpDefaultResponse, IpFullResponse};
+use model::edns_response::EdnsResponse;
+use model::ip_response::{IpDefaultResponse, IpFullResponse, IpResponse};
 
+use crate::builder::IpApiClientBuilder;
+use crate::cache::{CacheKey, ResponseCache};
 use crate::client::{AsyncIpApi, IpApi};
 use crate::error::IpApiError;
+use crate::field::{Field, FieldSet};
+use crate::format::ResponseFormat;
+use crate::language::Language;
+use crate::query::{IntoQuery, Query};
+use crate::util::ip_ranges;
+use crate::util::rate_limit::{RateLimitHandling, ServerRateLimit};
+use crate::util::retry::RetryPolicy;
+use crate::util::urls::{build_fields_param_from_field_set, build_fields_param_from_struct};
 
 #[cfg(feature = "blocking")]
 pub mod blocking;
+pub mod builder;
+pub mod cache;
 pub mod client;
 pub mod constant;
 pub mod error;
+pub mod field;
+pub mod format;
+pub mod language;
 pub mod model;
+pub mod monitor;
+pub mod query;
 pub mod request_handler;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub mod util;
 
 /// The main client for the ip-api.com API.
@@ -27,8 +48,31 @@ pub struct IpApiClient {
     pub client: Client,
     /// The rate limiter to use for the requests.
     pub limiter: Option<DefaultDirectRateLimiter>,
+    /// The rate limiter to use for the batch requests.
+    pub batch_limiter: Option<DefaultDirectRateLimiter>,
+    /// The rate limit the API reported for the requests.
+    pub server_limit: ServerRateLimit,
+    /// The rate limit the API reported for the batch requests.
+    pub batch_server_limit: ServerRateLimit,
+    /// How to handle requests the API rejected because the rate limit is exceeded.
+    pub rate_limit_handling: RateLimitHandling,
+    /// The policy for retrying requests that failed for transient reasons.
+    pub retry_policy: RetryPolicy,
+    /// Whether IP addresses in private and reserved ranges are rejected without sending a request.
+    pub reserved_range_check: bool,
     /// The API key to use for the requests.
+    /// With an API key all requests are sent to the pro tier.
     pub api_key: Option<String>,
+    /// The format the API answers single requests in.
+    pub response_format: ResponseFormat,
+    /// The default language of the responses, `None` uses the default of the API.
+    pub language: Option<Language>,
+    /// The custom base url of the API, `None` uses ip-api.com.
+    pub base_url: Option<String>,
+    /// The custom base url of the EDNS endpoint, `None` uses a random subdomain of edns.ip-api.com.
+    pub edns_base_url: Option<String>,
+    /// The cache for the responses of single requests, `None` disables caching.
+    pub cache: Option<Box<dyn ResponseCache>>,
 }
 
 impl Default for IpApiClient {
@@ -38,12 +82,23 @@ impl Default for IpApiClient {
 }
 
 impl IpApiClient {
-    /// Creates a new BlockingIpApiClient with no API key.
+    /// Creates a new IpApiClient with no API key.
     pub fn new() -> Self {
         Self {
             client: Client::new(),
             limiter: Some(RateLimiter::direct(Quota::per_minute(nonzero!(45u32)))),
+            batch_limiter: Some(RateLimiter::direct(Quota::per_minute(nonzero!(15u32)))),
+            server_limit: ServerRateLimit::new(),
+            batch_server_limit: ServerRateLimit::new(),
+            rate_limit_handling: RateLimitHandling::default(),
+            retry_policy: RetryPolicy::default(),
+            reserved_range_check: true,
             api_key: None,
+            response_format: ResponseFormat::default(),
+            language: None,
+            base_url: None,
+            edns_base_url: None,
+            cache: None,
         }
     }
 
@@ -52,9 +107,124 @@ impl IpApiClient {
         Self {
             client: Client::new(),
             limiter: None,
+            batch_limiter: None,
+            server_limit: ServerRateLimit::new(),
+            batch_server_limit: ServerRateLimit::new(),
+            rate_limit_handling: RateLimitHandling::default(),
+            retry_policy: RetryPolicy::default(),
+            reserved_range_check: true,
             api_key: Some(api_key),
+            response_format: ResponseFormat::default(),
+            language: None,
+            base_url: None,
+            edns_base_url: None,
+            cache: None,
         }
     }
+
+    /// Creates a builder to configure a new IpApiClient.
+    pub fn builder() -> IpApiClientBuilder {
+        IpApiClientBuilder::new()
+    }
+
+    /// Sets a custom base url of the API.
+    /// Requests are sent to `{base_url}/json/` and `{base_url}/batch` instead of ip-api.com.
+    ///
+    /// # Arguments
+    /// * `base_url` - The base url, like `http://localhost:8080`.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Sets a custom base url of the EDNS endpoint.
+    /// EDNS requests are sent to `{edns_base_url}/json` instead of a random subdomain of edns.ip-api.com.
+    ///
+    /// # Arguments
+    /// * `edns_base_url` - The base url, like `http://localhost:8080`.
+    pub fn with_edns_base_url(mut self, edns_base_url: impl Into<String>) -> Self {
+        self.edns_base_url = Some(edns_base_url.into());
+        self
+    }
+
+    /// Checks whether a query is rejected locally, because it is in a private or reserved range.
+    ///
+    /// # Arguments
+    /// * `query` - The query to check.
+    ///
+    /// # Returns
+    /// * `Option<IpApiError>` - The error the API would answer with or `None` if the query has to be sent.
+    fn check_reserved_range(&self, query: &str) -> Option<IpApiError> {
+        match self.reserved_range_check {
+            true => ip_ranges::check_query(query),
+            false => None,
+        }
+    }
+
+    /// Queries the API for a single IP address.
+    /// The fields are automatically extracted from the struct.
+    ///
+    /// # Arguments
+    /// * `query` - The query, already validated.
+    /// * `language` - The language of the response, `None` uses the default of the API.
+    ///
+    /// # Returns
+    /// * `T` - The response from the API.
+    async fn query<T>(&self, query: Query, language: Option<Language>) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        let fields = build_fields_param_from_struct::<T>();
+        self.query_with_fields::<T>(query, &fields, &format::columns::<T>(), language).await
+    }
+
+    /// Queries the API for a single IP address with the given fields.
+    /// Cached responses, except for the own IP address, and queries in private or reserved ranges are answered without sending a request.
+    ///
+    /// # Arguments
+    /// * `query` - The query, already validated.
+    /// * `fields` - The value of the `fields` parameter.
+    /// * `columns` - The requested fields, to decode responses that are not JSON.
+    /// * `language` - The language of the response, `None` uses the default of the API.
+    ///
+    /// # Returns
+    /// * `T` - The response from the API.
+    async fn query_with_fields<T>(
+        &self,
+        query: Query,
+        fields: &str,
+        columns: &[Field],
+        language: Option<Language>,
+    ) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        let ip = query.to_string();
+        if let Some(error) = self.check_reserved_range(&ip) {
+            return Err(error);
+        }
+        let key = CacheKey::new(&ip, fields, language);
+        let cache = self.cache.as_ref().filter(|_| query != Query::SelfIp);
+        if let Some(json) = cache.and_then(|cache| cache.get(&key)) {
+            return request_handler::process_result::<T>(json);
+        }
+        let request = util::requests::get_async_fields_request(&ip, fields, language, self.response_format, self);
+        let body = request_handler::send_request_with_format(
+            request,
+            &self.limiter,
+            &self.server_limit,
+            self.rate_limit_handling,
+            &self.retry_policy,
+            self.response_format,
+        )
+        .await?;
+        let json = format::decode_columns(self.response_format, body, &ip, columns)?;
+        let result = request_handler::process_result::<T>(json.clone());
+        if let (Ok(_), Some(cache)) = (&result, cache) {
+            cache.insert(key, json);
+        }
+        result
+    }
 }
 
 impl IpApi for IpApiClient {
@@ -62,28 +232,171 @@ impl IpApi for IpApiClient {
         &self.api_key
     }
 
+    fn get_base_url(&self) -> &Option<String> {
+        &self.base_url
+    }
+
+    fn get_edns_base_url(&self) -> &Option<String> {
+        &self.edns_base_url
+    }
+
+    fn get_response_format(&self) -> ResponseFormat {
+        self.response_format
+    }
+
+    fn get_language(&self) -> &Option<Language> {
+        &self.language
+    }
+
     fn get_rate_limiter(&self) -> &Option<DefaultDirectRateLimiter> {
         &self.limiter
     }
+
+    fn get_batch_rate_limiter(&self) -> &Option<DefaultDirectRateLimiter> {
+        &self.batch_limiter
+    }
+
+    fn get_server_rate_limit(&self) -> &ServerRateLimit {
+        &self.server_limit
+    }
+
+    fn get_batch_server_rate_limit(&self) -> &ServerRateLimit {
+        &self.batch_server_limit
+    }
+
+    fn get_rate_limit_handling(&self) -> RateLimitHandling {
+        self.rate_limit_handling
+    }
+
+    fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    fn get_reserved_range_check(&self) -> bool {
+        self.reserved_range_check
+    }
 }
 
 impl AsyncIpApi for IpApiClient {
-    async fn query_api_default(&self, ip: &str) -> Result<IpDefaultResponse, IpApiError> {
-        let request = util::requests::get_default_async_get_request(&ip.to_string(), self);
-        request_handler::perform_get_request::<IpDefaultResponse>(request, &self.limiter).await
+    async fn query_api_default(&self, ip: impl IntoQuery + Send) -> Result<IpDefaultResponse, IpApiError> {
+        self.query::<IpDefaultResponse>(ip.into_query()?, self.language).await
     }
 
-    async fn query_api_fully(&self, ip: &str) -> Result<IpFullResponse, IpApiError> {
-        let request = util::requests::get_async_request::<IpFullResponse>(&ip.to_string(), self);
-        request_handler::perform_get_request::<IpFullResponse>(request, &self.limiter).await
+    async fn query_api_fully(&self, ip: impl IntoQuery + Send) -> Result<IpFullResponse, IpApiError> {
+        self.query::<IpFullResponse>(ip.into_query()?, self.language).await
+    }
+
+    async fn query_api<T>(&self, ip: impl IntoQuery + Send) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.query::<T>(ip.into_query()?, self.language).await
     }
 
-    async fn query_api<T>(&self, ip: &str) -> Result<T, IpApiError>
+    async fn query_api_localized<T>(&self, ip: impl IntoQuery + Send, language: Language) -> Result<T, IpApiError>
     where
         T: DeserializeOwned,
     {
-        let request = util::requests::get_async_request::<T>(&ip.to_string(), self);
-        request_handler::perform_get_request::<T>(request, &self.limiter).await
+        self.query::<T>(ip.into_query()?, Some(language)).await
+    }
+
+    async fn query_fields(&self, ip: impl IntoQuery + Send, fields: &[Field]) -> Result<IpResponse, IpApiError> {
+        let fields = FieldSet::from_fields_or_default(fields);
+        let param = build_fields_param_from_field_set(fields);
+        self.query_with_fields::<IpResponse>(ip.into_query()?, &param, &fields.fields(), self.language).await
+    }
+
+    async fn query_api_raw<T>(&self, ip: impl IntoQuery + Send, format: ResponseFormat) -> Result<String, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        let ip = ip.into_query()?.to_string();
+        if let Some(error) = self.check_reserved_range(&ip) {
+            return Err(error);
+        }
+        let request = util::requests::get_async_format_request::<T>(&ip, self.language, format, self);
+        let body = request_handler::send_request_with_format(
+            request,
+            &self.limiter,
+            &self.server_limit,
+            self.rate_limit_handling,
+            &self.retry_policy,
+            format,
+        )
+        .await?;
+        request_handler::process_result::<IgnoredAny>(format::decode::<T>(format, body.clone(), &ip)?)?;
+        Ok(body)
+    }
+
+    async fn query_self<T>(&self) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.query::<T>(Query::SelfIp, self.language).await
+    }
+
+    async fn query_edns(&self) -> Result<EdnsResponse, IpApiError> {
+        let request = util::requests::get_async_edns_request(self);
+        request_handler::perform_get_request::<EdnsResponse>(
+            request,
+            &None,
+            &ServerRateLimit::new(),
+            self.rate_limit_handling,
+            &self.retry_policy,
+        )
+        .await
+    }
+
+    async fn query_api_batch<T>(
+        &self,
+        queries: &[impl IntoQuery + Clone + Sync],
+    ) -> Result<Vec<Result<T, IpApiError>>, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        if queries.len() > constant::BATCH_SIZE_LIMIT {
+            return Err(IpApiError::BatchTooLarge(queries.len()));
+        }
+        let (queries, local) =
+            request_handler::check_batch_queries(queries.iter().cloned(), |query| self.check_reserved_range(query));
+        let queries: Vec<&str> = queries.iter().map(String::as_str).collect();
+        let remote = request_handler::filter_batch_queries(&queries, &local);
+        if remote.is_empty() {
+            return Ok(request_handler::merge_batch_results(local, Vec::new()));
+        }
+        let request = util::requests::get_async_batch_request::<T>(&remote, self);
+        let results = request_handler::perform_batch_request::<T>(
+            request,
+            &self.batch_limiter,
+            &self.batch_server_limit,
+            self.rate_limit_handling,
+            &self.retry_policy,
+        )
+        .await?;
+        Ok(request_handler::merge_batch_results(local, results))
+    }
+
+    fn query_api_bulk<T, I>(
+        &self,
+        queries: I,
+    ) -> impl Future<Output = Result<Vec<Result<T, IpApiError>>, IpApiError>> + Send
+    where
+        T: DeserializeOwned + Send,
+        I: IntoIterator,
+        I::Item: IntoQuery,
+    {
+        let queries: Vec<String> = queries.into_iter().map(request_handler::query_to_string).collect();
+        async move {
+            let mut results = Vec::with_capacity(queries.len());
+            for chunk in queries.chunks(constant::BATCH_SIZE_LIMIT) {
+                let chunk: Vec<&str> = chunk.iter().map(String::as_str).collect();
+                match self.query_api_batch::<T>(&chunk).await {
+                    Ok(chunk_results) => results.extend(chunk_results),
+                    Err(error) => results.extend(request_handler::fail_batch(&chunk, error)),
+                }
+            }
+            Ok(results)
+        }
     }
 
     fn get_http_client(&self) -> &Client {
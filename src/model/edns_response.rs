@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// A struct containing the response from the EDNS endpoint of the Ip Api.
+/// It reports the DNS resolver that looked up a random subdomain and the client subnet the resolver forwarded.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct EdnsResponse {
+    /// The resolver that asked the name server of the API.
+    pub dns: EdnsServer,
+    /// The EDNS client subnet the resolver forwarded, `None` if it did not forward one.
+    pub edns: Option<EdnsServer>,
+}
+
+/// A struct containing an address seen by the EDNS endpoint of the Ip Api.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct EdnsServer {
+    /// The IP address, or the subnet for the EDNS client subnet.
+    pub ip: String,
+    /// The country and the ISP of the address, like `Netherlands - Google`.
+    pub geo: String,
+}
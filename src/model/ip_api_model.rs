@@ -0,0 +1,78 @@
+//! A module that contains the trait for models whose fields are checked at compile time.
+//!
+//! With the `derive` feature, `#[derive(IpApiModel)]` checks that every field of a struct is a field of the API
+//! and precomputes the `fields` parameter, so a typo fails to compile instead of failing to parse the response.
+//! Flattened fields add the fields of their own model.
+
+use crate::field::{Field, FieldSet};
+
+#[cfg(feature = "derive")]
+pub use ip_api4rs_derive::IpApiModel;
+
+/// A model whose fields are known to be fields of the API.
+///
+/// # Example
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use ip_api4rs::field::Field;
+/// use ip_api4rs::model::ip_api_model::IpApiModel;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, IpApiModel)]
+/// struct Location {
+///     lat: f32,
+///     lon: f32,
+/// }
+///
+/// #[derive(Deserialize, IpApiModel)]
+/// struct Custom {
+///     #[serde(rename = "query")]
+///     ip: String,
+///     #[serde(flatten)]
+///     location: Location,
+/// }
+///
+/// assert_eq!(Custom::FIELDS, Field::Query | Field::Lat | Field::Lon);
+/// # }
+/// ```
+pub trait IpApiModel {
+    /// The fields the model requests.
+    const FIELDS: FieldSet;
+    /// The digits of the `fields` parameter, right-aligned and padded with zeros.
+    #[doc(hidden)]
+    const FIELDS_DIGITS: [u8; 10] = fields_digits(Self::FIELDS.with(Field::Message));
+    /// The value of the `fields` parameter, which also requests the `message` field.
+    /// It is the same value `util::urls::build_fields_param_from_struct` builds at runtime.
+    const FIELDS_PARAM: &'static str = fields_param(&Self::FIELDS_DIGITS);
+}
+
+/// A flattened optional model requests the same fields as the model.
+impl<T: IpApiModel> IpApiModel for Option<T> {
+    const FIELDS: FieldSet = T::FIELDS;
+}
+
+/// Writes the numeric representation of a set of fields as digits.
+const fn fields_digits(fields: FieldSet) -> [u8; 10] {
+    let mut digits = [b'0'; 10];
+    let mut bits = fields.bits();
+    let mut index = digits.len();
+    while bits > 0 {
+        index -= 1;
+        digits[index] = b'0' + (bits % 10) as u8;
+        bits /= 10;
+    }
+    digits
+}
+
+/// Gets the digits without the padding as string.
+const fn fields_param(digits: &'static [u8; 10]) -> &'static str {
+    let mut start = 0;
+    while start < digits.len() - 1 && digits[start] == b'0' {
+        start += 1;
+    }
+    match std::str::from_utf8(digits.split_at(start).1) {
+        Ok(param) => param,
+        Err(_) => panic!("the digits are ASCII"),
+    }
+}
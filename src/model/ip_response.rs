@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::field::Field;
+use crate::util::deserialize::empty_string_as_none;
 
 /// A struct containing the complete response from the Ip Api.
 /// We ask the Api to return every field it can.
@@ -58,10 +62,300 @@ pub struct IpDefaultResponse {
     pub asn: String,
 }
 
+/// A struct containing a response with fields that were chosen at runtime.
+/// It holds the fields the API returned, the accessors return `None` for fields that were not requested.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+#[serde(transparent)]
+pub struct IpResponse {
+    values: Map<String, Value>,
+}
+
+impl IpResponse {
+    /// Gets the raw value of a field.
+    ///
+    /// # Arguments
+    /// * `field` - The field.
+    ///
+    /// # Returns
+    /// * `Option<&Value>` - The value or `None` if the response does not contain the field.
+    pub fn get(&self, field: Field) -> Option<&Value> {
+        self.values.get(field.name())
+    }
+
+    /// Gets the fields the response contains, in the order the API documents them.
+    pub fn fields(&self) -> Vec<Field> {
+        Field::ALL.into_iter().filter(|field| self.values.contains_key(field.name())).collect()
+    }
+
+    /// Gets the raw values of the response, keyed by the names of the fields.
+    pub fn values(&self) -> &Map<String, Value> {
+        &self.values
+    }
+
+    /// Gets the text of a field.
+    fn text(&self, field: Field) -> Option<&str> {
+        self.get(field).and_then(Value::as_str)
+    }
+
+    /// Gets `success` or `fail`.
+    pub fn status(&self) -> Option<&str> {
+        self.text(Field::Status)
+    }
+
+    /// Gets the error message.
+    pub fn message(&self) -> Option<&str> {
+        self.text(Field::Message)
+    }
+
+    /// Gets the continent name.
+    pub fn continent(&self) -> Option<&str> {
+        self.text(Field::Continent)
+    }
+
+    /// Gets the two-letter continent code.
+    pub fn continent_code(&self) -> Option<&str> {
+        self.text(Field::ContinentCode)
+    }
+
+    /// Gets the country name.
+    pub fn country(&self) -> Option<&str> {
+        self.text(Field::Country)
+    }
+
+    /// Gets the two-letter country code.
+    pub fn country_code(&self) -> Option<&str> {
+        self.text(Field::CountryCode)
+    }
+
+    /// Gets the region or state short code.
+    pub fn region(&self) -> Option<&str> {
+        self.text(Field::Region)
+    }
+
+    /// Gets the region or state name.
+    pub fn region_name(&self) -> Option<&str> {
+        self.text(Field::RegionName)
+    }
+
+    /// Gets the city name.
+    pub fn city(&self) -> Option<&str> {
+        self.text(Field::City)
+    }
+
+    /// Gets the district.
+    pub fn district(&self) -> Option<&str> {
+        self.text(Field::District)
+    }
+
+    /// Gets the zip code.
+    pub fn zip(&self) -> Option<&str> {
+        self.text(Field::Zip)
+    }
+
+    /// Gets the latitude.
+    pub fn lat(&self) -> Option<f64> {
+        self.get(Field::Lat).and_then(Value::as_f64)
+    }
+
+    /// Gets the longitude.
+    pub fn lon(&self) -> Option<f64> {
+        self.get(Field::Lon).and_then(Value::as_f64)
+    }
+
+    /// Gets the timezone.
+    pub fn timezone(&self) -> Option<&str> {
+        self.text(Field::Timezone)
+    }
+
+    /// Gets the timezone UTC DST offset in seconds.
+    pub fn offset(&self) -> Option<i64> {
+        self.get(Field::Offset).and_then(Value::as_i64)
+    }
+
+    /// Gets the national currency.
+    pub fn currency(&self) -> Option<&str> {
+        self.text(Field::Currency)
+    }
+
+    /// Gets the ISP name.
+    pub fn isp(&self) -> Option<&str> {
+        self.text(Field::Isp)
+    }
+
+    /// Gets the organization name.
+    pub fn org(&self) -> Option<&str> {
+        self.text(Field::Org)
+    }
+
+    /// Gets the AS number and organization.
+    pub fn asn(&self) -> Option<&str> {
+        self.text(Field::As)
+    }
+
+    /// Gets the AS name.
+    pub fn as_name(&self) -> Option<&str> {
+        self.text(Field::AsName)
+    }
+
+    /// Gets the reverse DNS of the IP.
+    pub fn reverse(&self) -> Option<&str> {
+        self.text(Field::Reverse)
+    }
+
+    /// Gets whether the IP belongs to a mobile connection.
+    pub fn mobile(&self) -> Option<bool> {
+        self.get(Field::Mobile).and_then(Value::as_bool)
+    }
+
+    /// Gets whether the IP belongs to a proxy, VPN or Tor exit address.
+    pub fn proxy(&self) -> Option<bool> {
+        self.get(Field::Proxy).and_then(Value::as_bool)
+    }
+
+    /// Gets whether the IP belongs to a hosting, colocated or data center.
+    pub fn hosting(&self) -> Option<bool> {
+        self.get(Field::Hosting).and_then(Value::as_bool)
+    }
+
+    /// Gets the IP used for the query.
+    pub fn query(&self) -> Option<&str> {
+        self.text(Field::Query)
+    }
+}
+
+impl From<IpResponse> for Map<String, Value> {
+    fn from(response: IpResponse) -> Self {
+        response.values
+    }
+}
+
+/// A struct containing any response from the Ip Api, with every field optional.
+/// Fields that were not requested, are missing or are empty, like the district of most IP addresses, are `None`.
+/// Use `IpFullResponse` or `IpDefaultResponse` to require every field instead,
+/// or `IpResponse` to keep the raw values of fields chosen at runtime.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+#[serde(default)]
+pub struct IpUniversalResponse {
+    /// `success` or `fail`.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub status: Option<String>,
+    /// The error message, only included when the status is `fail`.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub message: Option<String>,
+    /// The continent name.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub continent: Option<String>,
+    /// The two-letter continent code.
+    #[serde(rename = "continentCode", deserialize_with = "empty_string_as_none")]
+    pub continent_code: Option<String>,
+    /// The country name.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub country: Option<String>,
+    /// The two-letter country code.
+    #[serde(rename = "countryCode", deserialize_with = "empty_string_as_none")]
+    pub country_code: Option<String>,
+    /// The region or state short code.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub region: Option<String>,
+    /// The region or state name.
+    #[serde(rename = "regionName", deserialize_with = "empty_string_as_none")]
+    pub region_name: Option<String>,
+    /// The city name.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub city: Option<String>,
+    /// The district.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub district: Option<String>,
+    /// The zip code.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub zip: Option<String>,
+    /// The latitude.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub lat: Option<f32>,
+    /// The longitude.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub lon: Option<f32>,
+    /// The timezone.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub timezone: Option<String>,
+    /// The timezone UTC DST offset in seconds.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub offset: Option<i32>,
+    /// The national currency.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub currency: Option<String>,
+    /// The ISP name.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub isp: Option<String>,
+    /// The organization name.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub org: Option<String>,
+    /// The AS number and organization.
+    #[serde(rename = "as", deserialize_with = "empty_string_as_none")]
+    pub asn: Option<String>,
+    /// The AS name.
+    #[serde(rename = "asname", deserialize_with = "empty_string_as_none")]
+    pub as_name: Option<String>,
+    /// The reverse DNS of the IP.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub reverse: Option<String>,
+    /// Whether the IP belongs to a mobile connection.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub mobile: Option<bool>,
+    /// Whether the IP belongs to a proxy, VPN or Tor exit address.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub proxy: Option<bool>,
+    /// Whether the IP belongs to a hosting, colocated or data center.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub hosting: Option<bool>,
+    /// The IP used for the query.
+    #[serde(deserialize_with = "empty_string_as_none")]
+    pub query: Option<String>,
+}
+
+impl IpUniversalResponse {
+    /// Gets the fields the response contains, in the order the API documents them.
+    ///
+    /// # Returns
+    /// * `Vec<Field>` - The fields that are not `None`.
+    pub fn fields(&self) -> Vec<Field> {
+        let present = [
+            (Field::Status, self.status.is_some()),
+            (Field::Message, self.message.is_some()),
+            (Field::Continent, self.continent.is_some()),
+            (Field::ContinentCode, self.continent_code.is_some()),
+            (Field::Country, self.country.is_some()),
+            (Field::CountryCode, self.country_code.is_some()),
+            (Field::Region, self.region.is_some()),
+            (Field::RegionName, self.region_name.is_some()),
+            (Field::City, self.city.is_some()),
+            (Field::District, self.district.is_some()),
+            (Field::Zip, self.zip.is_some()),
+            (Field::Lat, self.lat.is_some()),
+            (Field::Lon, self.lon.is_some()),
+            (Field::Timezone, self.timezone.is_some()),
+            (Field::Offset, self.offset.is_some()),
+            (Field::Currency, self.currency.is_some()),
+            (Field::Isp, self.isp.is_some()),
+            (Field::Org, self.org.is_some()),
+            (Field::As, self.asn.is_some()),
+            (Field::AsName, self.as_name.is_some()),
+            (Field::Reverse, self.reverse.is_some()),
+            (Field::Mobile, self.mobile.is_some()),
+            (Field::Proxy, self.proxy.is_some()),
+            (Field::Hosting, self.hosting.is_some()),
+            (Field::Query, self.query.is_some()),
+        ];
+        present.into_iter().filter(|(_, present)| *present).map(|(field, _)| field).collect()
+    }
+}
+
 /// A module that contains the error type for the library.
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 pub struct ErrorResponse {
     pub status: String,
     pub message: String,
+    /// Some failed responses, like the one for HTTPS without an API key, do not contain the query.
+    #[serde(default)]
     pub query: String,
 }
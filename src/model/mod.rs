@@ -21,4 +21,6 @@
 //! }
 //! ```
 
+pub mod edns_response;
+pub mod ip_api_model;
 pub mod ip_response;
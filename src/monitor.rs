@@ -0,0 +1,284 @@
+//! A module that contains a monitor that reports changes of the own public IP address.
+//!
+//! The monitor polls the API for the own IP address and reports a change of the address, the ISP or the country,
+//! like a failover of the uplink to a different carrier.
+//!
+//! # Example
+//! ```no_run
+//! use std::time::Duration;
+//! use ip_api4rs::IpApiClient;
+//! use ip_api4rs::monitor::{MonitorEvent, PublicIpMonitor};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut monitor = PublicIpMonitor::new(IpApiClient::new(), Duration::from_secs(60)).unwrap();
+//!     loop {
+//!         match monitor.next_event().await {
+//!             MonitorEvent::Changed(change) => println!("{:?}", change.current),
+//!             MonitorEvent::Error(error) => eprintln!("{}", error),
+//!         }
+//!     }
+//! }
+//! ```
+
+#[cfg(feature = "blocking")]
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+#[cfg(feature = "blocking")]
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use futures_timer::Delay;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "blocking")]
+use crate::client::BlockingIpApi;
+use crate::client::{AsyncIpApi, IpApi};
+use crate::error::IpApiError;
+
+/// The smallest interval that stays within the 45 requests per minute of the free tier.
+pub const MIN_FREE_TIER_INTERVAL: Duration = Duration::from_millis(1334);
+
+/// The part of the response the monitor watches.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct PublicIpInfo {
+    #[serde(rename = "query")]
+    pub ip: String,
+    pub status: String,
+    pub isp: String,
+    pub country: String,
+    #[serde(rename = "countryCode")]
+    pub country_code: String,
+}
+
+/// The properties of the public IP address a change is reported for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangedProperty {
+    /// The IP address itself.
+    Ip,
+    /// The ISP the address belongs to.
+    Isp,
+    /// The country the address is located in.
+    Country,
+}
+
+/// A change of the public IP address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublicIpChange {
+    /// The last observed state, `None` for the first observation.
+    pub previous: Option<PublicIpInfo>,
+    /// The current state.
+    pub current: PublicIpInfo,
+    /// The properties that changed, empty for the first observation.
+    pub changed: Vec<ChangedProperty>,
+}
+
+/// An event of the monitor.
+#[derive(Debug)]
+pub enum MonitorEvent {
+    /// The public IP address was observed for the first time or changed.
+    Changed(Box<PublicIpChange>),
+    /// The public IP address could not be queried, the monitor keeps polling.
+    Error(IpApiError),
+}
+
+/// A monitor that polls the API for the own public IP address at a fixed interval.
+#[derive(Debug)]
+pub struct PublicIpMonitor<C> {
+    client: C,
+    interval: Duration,
+    last: Option<PublicIpInfo>,
+    next_poll_at: Option<Instant>,
+}
+
+impl<C> PublicIpMonitor<C>
+where
+    C: IpApi,
+{
+    /// Creates a new PublicIpMonitor.
+    /// Without an API key the interval must keep the monitor within the quota of the free tier.
+    ///
+    /// # Arguments
+    /// * `client` - The client to poll with.
+    /// * `interval` - The time between two polls.
+    ///
+    /// # Returns
+    /// * `Result<PublicIpMonitor<C>, IpApiError>` - The monitor or `IpApiError::InvalidConfiguration`.
+    pub fn new(client: C, interval: Duration) -> Result<Self, IpApiError> {
+        if client.get_api_key().is_none() && interval < MIN_FREE_TIER_INTERVAL {
+            return Err(IpApiError::InvalidConfiguration(
+                "The interval exceeds the quota of the free tier.".to_string(),
+            ));
+        }
+        Ok(Self {
+            client,
+            interval,
+            last: None,
+            next_poll_at: None,
+        })
+    }
+
+    /// Gets the last observed state.
+    pub fn last(&self) -> Option<&PublicIpInfo> {
+        self.last.as_ref()
+    }
+
+    /// Gets the client the monitor polls with.
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Records an observed state.
+    ///
+    /// # Arguments
+    /// * `current` - The observed state.
+    ///
+    /// # Returns
+    /// * `Option<PublicIpChange>` - The change or `None` if nothing the monitor watches changed.
+    pub fn observe(&mut self, current: PublicIpInfo) -> Option<PublicIpChange> {
+        let changed = match &self.last {
+            Some(previous) => compare(previous, &current),
+            None => Vec::new(),
+        };
+        if self.last.is_some() && changed.is_empty() {
+            return None;
+        }
+        let previous = self.last.replace(current.clone());
+        Some(PublicIpChange {
+            previous,
+            current,
+            changed,
+        })
+    }
+
+    /// Gets the time to wait before the next poll and schedules the one after it.
+    fn schedule(&mut self) -> Duration {
+        let now = Instant::now();
+        let wait = self.next_poll_at.map(|next_poll_at| next_poll_at.saturating_duration_since(now));
+        self.next_poll_at = Some(now + wait.unwrap_or_default() + self.interval);
+        wait.unwrap_or_default()
+    }
+
+    /// Turns the result of a poll into an event.
+    fn handle(&mut self, result: Result<PublicIpInfo, IpApiError>) -> Option<MonitorEvent> {
+        match result {
+            Ok(current) => self.observe(current).map(|change| MonitorEvent::Changed(Box::new(change))),
+            Err(error) => Some(MonitorEvent::Error(error)),
+        }
+    }
+}
+
+impl<C> PublicIpMonitor<C>
+where
+    C: AsyncIpApi,
+{
+    /// Polls the API until the public IP address changes or an error occurs.
+    /// The first call reports the current state right away.
+    ///
+    /// # Returns
+    /// * `MonitorEvent` - The next event.
+    pub async fn next_event(&mut self) -> MonitorEvent {
+        loop {
+            Delay::new(self.schedule()).await;
+            let result = self.client.query_self::<PublicIpInfo>().await;
+            if let Some(event) = self.handle(result) {
+                return event;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<C> PublicIpMonitor<C>
+where
+    C: BlockingIpApi + Send + 'static,
+{
+    /// Polls the API until the public IP address changes or an error occurs, blocking the current thread.
+    /// The first call reports the current state right away.
+    ///
+    /// # Returns
+    /// * `MonitorEvent` - The next event.
+    pub fn next_event_blocking(&mut self) -> MonitorEvent {
+        loop {
+            thread::sleep(self.schedule());
+            let result = self.client.query_self::<PublicIpInfo>();
+            if let Some(event) = self.handle(result) {
+                return event;
+            }
+        }
+    }
+
+    /// Polls the API in a background thread and calls the callback for every event.
+    ///
+    /// # Arguments
+    /// * `callback` - Called with every event.
+    ///
+    /// # Returns
+    /// * `PublicIpMonitorHandle` - The handle that stops the monitor when it is dropped.
+    pub fn spawn<F>(mut self, mut callback: F) -> PublicIpMonitorHandle
+    where
+        F: FnMut(MonitorEvent) + Send + 'static,
+    {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let handle = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(self.schedule()) {
+                let result = self.client.query_self::<PublicIpInfo>();
+                if let Some(event) = self.handle(result) {
+                    callback(event);
+                }
+            }
+        });
+        PublicIpMonitorHandle {
+            stop: Some(stop),
+            handle: Some(handle),
+        }
+    }
+}
+
+/// The handle of a monitor that runs in a background thread.
+/// The monitor stops when the handle is dropped.
+#[cfg(feature = "blocking")]
+#[derive(Debug)]
+pub struct PublicIpMonitorHandle {
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+#[cfg(feature = "blocking")]
+impl PublicIpMonitorHandle {
+    /// Stops the monitor and waits for a running poll to finish.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    /// Signals the background thread to stop and joins it.
+    fn shutdown(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Drop for PublicIpMonitorHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Compares two states of the public IP address.
+fn compare(previous: &PublicIpInfo, current: &PublicIpInfo) -> Vec<ChangedProperty> {
+    let mut changed = Vec::new();
+    if previous.ip != current.ip {
+        changed.push(ChangedProperty::Ip);
+    }
+    if previous.isp != current.isp {
+        changed.push(ChangedProperty::Isp);
+    }
+    if previous.country_code != current.country_code {
+        changed.push(ChangedProperty::Country);
+    }
+    changed
+}
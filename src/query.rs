@@ -0,0 +1,190 @@
+//! A module that contains the typed input of the query methods.
+
+use std::fmt::{Display, Formatter};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use crate::constant;
+use crate::error::IpApiError;
+use crate::model::ip_response::ErrorResponse;
+
+/// The maximum length of a hostname.
+const MAX_HOST_LENGTH: usize = 253;
+
+/// The maximum length of a single label of a hostname.
+const MAX_LABEL_LENGTH: usize = 63;
+
+/// What to look up with the API.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Query {
+    /// An IPv4 or IPv6 address.
+    Ip(IpAddr),
+    /// A hostname, which the API resolves before looking it up.
+    Host(String),
+    /// The public IP address of the caller.
+    SelfIp,
+}
+
+impl Query {
+    /// Creates a new query for a hostname.
+    ///
+    /// # Arguments
+    /// * `host` - The hostname, like `example.com`.
+    ///
+    /// # Returns
+    /// * `Result<Query, IpApiError>` - The query or `IpApiError::InvalidQuery` if the hostname is invalid.
+    pub fn host(host: impl Into<String>) -> Result<Self, IpApiError> {
+        let host = host.into();
+        match is_valid_host(&host) {
+            true => Ok(Query::Host(host)),
+            false => Err(invalid_query(&host)),
+        }
+    }
+
+    /// Parses a query.
+    /// An empty string looks up the public IP address of the caller.
+    ///
+    /// # Arguments
+    /// * `query` - An IP address, a hostname or an empty string.
+    ///
+    /// # Returns
+    /// * `Result<Query, IpApiError>` - The query or `IpApiError::InvalidQuery` if it is neither.
+    pub fn parse(query: &str) -> Result<Self, IpApiError> {
+        if query.is_empty() {
+            return Ok(Query::SelfIp);
+        }
+        match query.parse::<IpAddr>() {
+            Ok(ip) => Ok(Query::Ip(ip)),
+            Err(_) => Query::host(query),
+        }
+    }
+
+    /// Gets the IP address of the query.
+    ///
+    /// # Returns
+    /// * `Option<IpAddr>` - The IP address or `None` if the query is not an IP address.
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self {
+            Query::Ip(ip) => Some(*ip),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Query::Ip(ip) => ip.fmt(f),
+            Query::Host(host) => f.write_str(host),
+            Query::SelfIp => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = IpApiError;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        Query::parse(query)
+    }
+}
+
+impl From<IpAddr> for Query {
+    fn from(ip: IpAddr) -> Self {
+        Query::Ip(ip)
+    }
+}
+
+impl From<Ipv4Addr> for Query {
+    fn from(ip: Ipv4Addr) -> Self {
+        Query::Ip(IpAddr::V4(ip))
+    }
+}
+
+impl From<Ipv6Addr> for Query {
+    fn from(ip: Ipv6Addr) -> Self {
+        Query::Ip(IpAddr::V6(ip))
+    }
+}
+
+/// A value that can be turned into a `Query`.
+/// Strings are parsed and rejected before any request is built, if they are neither an IP address nor a hostname.
+pub trait IntoQuery {
+    /// Turns the value into a `Query`.
+    ///
+    /// # Returns
+    /// * `Result<Query, IpApiError>` - The query or `IpApiError::InvalidQuery`.
+    fn into_query(self) -> Result<Query, IpApiError>;
+}
+
+impl IntoQuery for Query {
+    fn into_query(self) -> Result<Query, IpApiError> {
+        Ok(self)
+    }
+}
+
+impl IntoQuery for &Query {
+    fn into_query(self) -> Result<Query, IpApiError> {
+        Ok(self.clone())
+    }
+}
+
+impl IntoQuery for &str {
+    fn into_query(self) -> Result<Query, IpApiError> {
+        Query::parse(self)
+    }
+}
+
+impl IntoQuery for String {
+    fn into_query(self) -> Result<Query, IpApiError> {
+        Query::parse(&self)
+    }
+}
+
+impl IntoQuery for &String {
+    fn into_query(self) -> Result<Query, IpApiError> {
+        Query::parse(self)
+    }
+}
+
+impl IntoQuery for IpAddr {
+    fn into_query(self) -> Result<Query, IpApiError> {
+        Ok(Query::from(self))
+    }
+}
+
+impl IntoQuery for Ipv4Addr {
+    fn into_query(self) -> Result<Query, IpApiError> {
+        Ok(Query::from(self))
+    }
+}
+
+impl IntoQuery for Ipv6Addr {
+    fn into_query(self) -> Result<Query, IpApiError> {
+        Ok(Query::from(self))
+    }
+}
+
+/// Checks whether a string is a valid hostname.
+/// Every label must consist of letters, digits and hyphens and must not start or end with a hyphen.
+fn is_valid_host(host: &str) -> bool {
+    let host = host.strip_suffix('.').unwrap_or(host);
+    !host.is_empty()
+        && host.len() <= MAX_HOST_LENGTH
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= MAX_LABEL_LENGTH
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|char| char.is_ascii_alphanumeric() || char == '-')
+        })
+}
+
+/// Creates the `IpApiError::InvalidQuery` the API would answer an invalid query with.
+fn invalid_query(query: &str) -> IpApiError {
+    IpApiError::InvalidQuery(ErrorResponse {
+        status: "fail".to_string(),
+        message: constant::ERROR_INVALID_QUERY.to_string(),
+        query: query.to_string(),
+    })
+}
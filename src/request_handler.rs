@@ -3,15 +3,21 @@
 
 use crate::constant;
 use crate::error::IpApiError;
+use crate::format::ResponseFormat;
 use crate::model::ip_response::ErrorResponse;
+use crate::query::IntoQuery;
+use crate::util::rate_limit::{RateLimitHandling, ServerRateLimit};
+use crate::util::retry::RetryPolicy;
+use crate::util::urls::query_from_url;
+use futures_timer::Delay;
 use governor::DefaultDirectRateLimiter;
 #[cfg(feature = "blocking")]
 use reqwest::blocking;
-use reqwest::RequestBuilder;
-use serde::de::DeserializeOwned;
+use reqwest::{RequestBuilder, StatusCode};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use std::sync::Arc;
 #[cfg(feature = "blocking")]
 use std::thread::sleep;
-#[cfg(feature = "blocking")]
 use std::time::Duration;
 
 /// Performs a GET request to the API.
@@ -19,27 +25,60 @@ use std::time::Duration;
 /// # Arguments
 /// * `request_builder` - The request builder to use.
 /// * `limiter` - The rate limiter to use.
+/// * `server_limit` - The rate limit reported by the API.
+/// * `handling` - How to handle an exceeded rate limit.
+/// * `retry` - The policy for retrying transient failures.
 ///
 /// # Returns
 /// * `Result<T, ip-api4rs::error::IpApiError>` - The response from the API.
 pub async fn perform_get_request<T>(
     request_builder: RequestBuilder,
     limiter: &Option<DefaultDirectRateLimiter>,
+    server_limit: &ServerRateLimit,
+    handling: RateLimitHandling,
+    retry: &RetryPolicy,
 ) -> Result<T, IpApiError>
 where
     T: DeserializeOwned,
 {
-    wait_for_rate_limiter(limiter).await;
-    let response = request_builder.send().await?;
-    let json = response.text().await?;
+    let json = send_request(request_builder, limiter, server_limit, handling, retry).await?;
     process_result(json)
 }
 
+/// Performs a batch request to the API.
+/// Every query of the batch gets its own result, in the order the queries were sent.
+///
+/// # Arguments
+/// * `request_builder` - The request builder to use.
+/// * `limiter` - The rate limiter to use.
+/// * `server_limit` - The rate limit reported by the API.
+/// * `handling` - How to handle an exceeded rate limit.
+/// * `retry` - The policy for retrying transient failures.
+///
+/// # Returns
+/// * `Result<Vec<Result<T, ip-api4rs::error::IpApiError>>, ip-api4rs::error::IpApiError>` - The responses from the API.
+pub async fn perform_batch_request<T>(
+    request_builder: RequestBuilder,
+    limiter: &Option<DefaultDirectRateLimiter>,
+    server_limit: &ServerRateLimit,
+    handling: RateLimitHandling,
+    retry: &RetryPolicy,
+) -> Result<Vec<Result<T, IpApiError>>, IpApiError>
+where
+    T: DeserializeOwned,
+{
+    let json = send_request(request_builder, limiter, server_limit, handling, retry).await?;
+    process_batch_result(&json)
+}
+
 /// Performs a blocking GET request to the API.
 ///
 /// # Arguments
 /// * `request_builder` - The request builder to use.
 /// * `limiter` - The rate limiter to use.
+/// * `server_limit` - The rate limit reported by the API.
+/// * `handling` - How to handle an exceeded rate limit.
+/// * `retry` - The policy for retrying transient failures.
 ///
 /// # Returns
 /// * `Result<T, ip-api4rs::error::IpApiError>` - The response from the API.
@@ -47,16 +86,313 @@ where
 pub fn perform_blocking_get_request<T>(
     request_builder: blocking::RequestBuilder,
     limiter: &Option<DefaultDirectRateLimiter>,
+    server_limit: &ServerRateLimit,
+    handling: RateLimitHandling,
+    retry: &RetryPolicy,
 ) -> Result<T, IpApiError>
 where
     T: DeserializeOwned,
 {
-    block_until_rate_limiter(limiter);
-    let response = request_builder.send()?;
-    let json = response.text()?;
+    let json = send_blocking_request(request_builder, limiter, server_limit, handling, retry)?;
     process_result::<T>(json)
 }
 
+/// Performs a blocking batch request to the API.
+/// Every query of the batch gets its own result, in the order the queries were sent.
+///
+/// # Arguments
+/// * `request_builder` - The request builder to use.
+/// * `limiter` - The rate limiter to use.
+/// * `server_limit` - The rate limit reported by the API.
+/// * `handling` - How to handle an exceeded rate limit.
+/// * `retry` - The policy for retrying transient failures.
+///
+/// # Returns
+/// * `Result<Vec<Result<T, ip-api4rs::error::IpApiError>>, ip-api4rs::error::IpApiError>` - The responses from the API.
+#[cfg(feature = "blocking")]
+pub fn perform_blocking_batch_request<T>(
+    request_builder: blocking::RequestBuilder,
+    limiter: &Option<DefaultDirectRateLimiter>,
+    server_limit: &ServerRateLimit,
+    handling: RateLimitHandling,
+    retry: &RetryPolicy,
+) -> Result<Vec<Result<T, IpApiError>>, IpApiError>
+where
+    T: DeserializeOwned,
+{
+    let json = send_blocking_request(request_builder, limiter, server_limit, handling, retry)?;
+    process_batch_result::<T>(&json)
+}
+
+/// Sends a request to the API once the rate limits allow it.
+/// An exceeded rate limit is either waited out or returned as an error, depending on the `handling`.
+/// It is waited out at most `constant::MAX_RATE_LIMIT_WAITS` times.
+/// Transient failures are retried according to the `retry` policy, every retry waits for the rate limits again.
+///
+/// # Arguments
+/// * `request_builder` - The request builder to use.
+/// * `limiter` - The rate limiter to use.
+/// * `server_limit` - The rate limit reported by the API.
+/// * `handling` - How to handle an exceeded rate limit.
+/// * `retry` - The policy for retrying transient failures.
+///
+/// # Returns
+/// * `Result<String, ip-api4rs::error::IpApiError>` - The body of the response.
+pub async fn send_request(
+    request_builder: RequestBuilder,
+    limiter: &Option<DefaultDirectRateLimiter>,
+    server_limit: &ServerRateLimit,
+    handling: RateLimitHandling,
+    retry: &RetryPolicy,
+) -> Result<String, IpApiError> {
+    send_request_with_format(request_builder, limiter, server_limit, handling, retry, ResponseFormat::Json).await
+}
+
+/// Sends a request to the API, that is answered in the given format, once the rate limits allow it.
+/// Only JSON bodies are checked here, the other formats are checked when they are decoded.
+///
+/// # Arguments
+/// * `request_builder` - The request builder to use.
+/// * `limiter` - The rate limiter to use.
+/// * `server_limit` - The rate limit reported by the API.
+/// * `handling` - How to handle an exceeded rate limit.
+/// * `retry` - The policy for retrying transient failures.
+/// * `format` - The format the API answers in.
+///
+/// # Returns
+/// * `Result<String, ip-api4rs::error::IpApiError>` - The body of the response.
+pub async fn send_request_with_format(
+    request_builder: RequestBuilder,
+    limiter: &Option<DefaultDirectRateLimiter>,
+    server_limit: &ServerRateLimit,
+    handling: RateLimitHandling,
+    retry: &RetryPolicy,
+    format: ResponseFormat,
+) -> Result<String, IpApiError> {
+    let mut attempt = 1;
+    let mut waits = 0;
+    loop {
+        wait_for_server_rate_limit(server_limit).await;
+        wait_for_rate_limiter(limiter).await;
+        let request = request_builder.try_clone().ok_or(IpApiError::Unknown(UNCLONEABLE_REQUEST.to_string()))?;
+        let error = match request.send().await {
+            Ok(response) => {
+                server_limit.update(response.headers());
+                match check_rate_limited(response.status(), server_limit) {
+                    Some(retry_after)
+                        if handling == RateLimitHandling::Wait && waits < constant::MAX_RATE_LIMIT_WAITS =>
+                    {
+                        waits += 1;
+                        Delay::new(retry_after).await;
+                        continue;
+                    }
+                    Some(retry_after) => IpApiError::RateLimited {
+                        retry_after,
+                    },
+                    None => {
+                        let status = response.status();
+                        let query = query_from_url(response.url());
+                        match response.text().await {
+                            Ok(body) if status.is_success() => return check_body(body, query, format),
+                            Ok(body) => check_status(status, query, body),
+                            Err(err) => IpApiError::ReqwestError(err),
+                        }
+                    }
+                }
+            }
+            Err(err) => IpApiError::ReqwestError(err),
+        };
+        match retry.delay_for(&error, attempt) {
+            Some(delay) => Delay::new(delay).await,
+            None => return Err(error),
+        }
+        attempt += 1;
+    }
+}
+
+/// Sends a blocking request to the API once the rate limits allow it.
+/// An exceeded rate limit is either waited out or returned as an error, depending on the `handling`.
+/// It is waited out at most `constant::MAX_RATE_LIMIT_WAITS` times.
+/// Transient failures are retried according to the `retry` policy, every retry waits for the rate limits again.
+///
+/// # Arguments
+/// * `request_builder` - The request builder to use.
+/// * `limiter` - The rate limiter to use.
+/// * `server_limit` - The rate limit reported by the API.
+/// * `handling` - How to handle an exceeded rate limit.
+/// * `retry` - The policy for retrying transient failures.
+///
+/// # Returns
+/// * `Result<String, ip-api4rs::error::IpApiError>` - The body of the response.
+#[cfg(feature = "blocking")]
+pub fn send_blocking_request(
+    request_builder: blocking::RequestBuilder,
+    limiter: &Option<DefaultDirectRateLimiter>,
+    server_limit: &ServerRateLimit,
+    handling: RateLimitHandling,
+    retry: &RetryPolicy,
+) -> Result<String, IpApiError> {
+    send_blocking_request_with_format(request_builder, limiter, server_limit, handling, retry, ResponseFormat::Json)
+}
+
+/// Sends a blocking request to the API, that is answered in the given format, once the rate limits allow it.
+/// Only JSON bodies are checked here, the other formats are checked when they are decoded.
+///
+/// # Arguments
+/// * `request_builder` - The request builder to use.
+/// * `limiter` - The rate limiter to use.
+/// * `server_limit` - The rate limit reported by the API.
+/// * `handling` - How to handle an exceeded rate limit.
+/// * `retry` - The policy for retrying transient failures.
+/// * `format` - The format the API answers in.
+///
+/// # Returns
+/// * `Result<String, ip-api4rs::error::IpApiError>` - The body of the response.
+#[cfg(feature = "blocking")]
+pub fn send_blocking_request_with_format(
+    request_builder: blocking::RequestBuilder,
+    limiter: &Option<DefaultDirectRateLimiter>,
+    server_limit: &ServerRateLimit,
+    handling: RateLimitHandling,
+    retry: &RetryPolicy,
+    format: ResponseFormat,
+) -> Result<String, IpApiError> {
+    let mut attempt = 1;
+    let mut waits = 0;
+    loop {
+        block_until_server_rate_limit(server_limit);
+        block_until_rate_limiter(limiter);
+        let request = request_builder.try_clone().ok_or(IpApiError::Unknown(UNCLONEABLE_REQUEST.to_string()))?;
+        let error = match request.send() {
+            Ok(response) => {
+                server_limit.update(response.headers());
+                match check_rate_limited(response.status(), server_limit) {
+                    Some(retry_after)
+                        if handling == RateLimitHandling::Wait && waits < constant::MAX_RATE_LIMIT_WAITS =>
+                    {
+                        waits += 1;
+                        sleep(retry_after);
+                        continue;
+                    }
+                    Some(retry_after) => IpApiError::RateLimited {
+                        retry_after,
+                    },
+                    None => {
+                        let status = response.status();
+                        let query = query_from_url(response.url());
+                        match response.text() {
+                            Ok(body) if status.is_success() => return check_body(body, query, format),
+                            Ok(body) => check_status(status, query, body),
+                            Err(err) => IpApiError::ReqwestError(err),
+                        }
+                    }
+                }
+            }
+            Err(err) => IpApiError::ReqwestError(err),
+        };
+        match retry.delay_for(&error, attempt) {
+            Some(delay) => sleep(delay),
+            None => return Err(error),
+        }
+        attempt += 1;
+    }
+}
+
+/// The message of the error returned when a request can not be cloned for sending.
+const UNCLONEABLE_REQUEST: &str = "The request can not be cloned.";
+
+/// Checks whether the API rejected the request because the rate limit is exceeded.
+/// The API answers with HTTP 429 in that case and the time to wait is taken from the `X-Ttl` header.
+///
+/// # Arguments
+/// * `status` - The status code of the response.
+/// * `server_limit` - The rate limit reported by the API, already updated from the response.
+///
+/// # Returns
+/// * `Option<Duration>` - The time to wait before retrying or `None` if the request was not rate limited.
+fn check_rate_limited(status: StatusCode, server_limit: &ServerRateLimit) -> Option<Duration> {
+    if status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let retry_after = server_limit.time_until_ready().unwrap_or(constant::DEFAULT_RETRY_AFTER);
+    server_limit.block_for(retry_after);
+    Some(retry_after)
+}
+
+/// Checks the body of a successful response, if the API answers in JSON.
+///
+/// # Arguments
+/// * `body` - The body of the response.
+/// * `query` - The query of the request, if it was a single request.
+/// * `format` - The format the API answers in.
+///
+/// # Returns
+/// * `Result<String, ip-api4rs::error::IpApiError>` - The body or `IpApiError::NonJsonBody`.
+fn check_body(body: String, query: Option<String>, format: ResponseFormat) -> Result<String, IpApiError> {
+    match format {
+        ResponseFormat::Json => check_json(body, query),
+        _ => Ok(body),
+    }
+}
+
+/// Checks that the body of a successful response is JSON.
+/// Proxies and captive portals answer with HTML, which would otherwise surface as a confusing parse error.
+///
+/// # Arguments
+/// * `body` - The body of the response.
+/// * `query` - The query of the request, if it was a single request.
+///
+/// # Returns
+/// * `Result<String, ip-api4rs::error::IpApiError>` - The body or `IpApiError::NonJsonBody`.
+fn check_json(body: String, query: Option<String>) -> Result<String, IpApiError> {
+    match serde_json::from_str::<IgnoredAny>(&body) {
+        Ok(_) => Ok(body),
+        Err(_) => Err(IpApiError::NonJsonBody {
+            body,
+            query,
+        }),
+    }
+}
+
+/// Turns a response with a status code other than success into an error.
+/// The API answers some failures, like HTTPS without an API key, with an error status and a failed response.
+///
+/// # Arguments
+/// * `status` - The status code of the response.
+/// * `query` - The query of the request, if it was a single request.
+/// * `body` - The body of the response.
+///
+/// # Returns
+/// * `IpApiError` - The error of the failed response or `IpApiError::HttpStatus`.
+fn check_status(status: StatusCode, query: Option<String>, body: String) -> IpApiError {
+    match validate_result(body) {
+        Some(IpApiError::Unknown(_)) | Some(IpApiError::JsonParseError(_)) | None => IpApiError::HttpStatus {
+            status: status.as_u16(),
+            query,
+        },
+        Some(error) => with_query(error, query),
+    }
+}
+
+/// Adds the query to the failed response of an error, if the API did not include it.
+fn with_query(mut error: IpApiError, query: Option<String>) -> IpApiError {
+    if let (
+        IpApiError::ReservedRange(response)
+        | IpApiError::PrivateRange(response)
+        | IpApiError::InvalidQuery(response)
+        | IpApiError::InvalidKey(response)
+        | IpApiError::SslUnavailable(response)
+        | IpApiError::QuotaExceeded(response),
+        Some(query),
+    ) = (&mut error, query)
+    {
+        if response.query.is_empty() {
+            response.query = query;
+        }
+    }
+    error
+}
+
 /// Processes the result from the API.
 /// Checks for errors and parses the result.
 ///
@@ -65,7 +401,7 @@ where
 ///
 /// # Returns
 /// * `Result<T, ip-api4rs::error::IpApiError>` - The parsed result.
-fn process_result<T>(json: String) -> Result<T, IpApiError>
+pub fn process_result<T>(json: String) -> Result<T, IpApiError>
 where
     T: DeserializeOwned,
 {
@@ -75,6 +411,128 @@ where
     parse_result::<T>(&json)
 }
 
+/// Processes the result of a batch request from the API.
+/// Every entry is checked for errors and parsed on its own.
+///
+/// # Arguments
+/// * `json` - The json array to parse.
+///
+/// # Returns
+/// * `Result<Vec<Result<T, ip-api4rs::error::IpApiError>>, ip-api4rs::error::IpApiError>` - The parsed results.
+fn process_batch_result<T>(json: &str) -> Result<Vec<Result<T, IpApiError>>, IpApiError>
+where
+    T: DeserializeOwned,
+{
+    let entries = serde_json::from_str::<Vec<serde_json::Value>>(json)?;
+    Ok(entries.into_iter().map(|entry| process_result::<T>(entry.to_string())).collect())
+}
+
+/// Filters the queries of a batch that were not answered locally.
+///
+/// # Arguments
+/// * `queries` - The queries of the batch.
+/// * `local` - The local answer of every query, `None` if the query has to be sent.
+///
+/// # Returns
+/// * `Vec<&str>` - The queries to send, in their original order.
+pub fn filter_batch_queries<'a>(queries: &[&'a str], local: &[Option<IpApiError>]) -> Vec<&'a str> {
+    queries.iter().zip(local).filter(|(_, error)| error.is_none()).map(|(query, _)| *query).collect()
+}
+
+/// Checks the queries of a batch locally, so only the valid ones are sent.
+///
+/// # Arguments
+/// * `queries` - The queries of the batch.
+/// * `check` - The check of a valid query, like the reserved range check of a client.
+///
+/// # Returns
+/// * `(Vec<String>, Vec<Option<IpApiError>>)` - The text of every query and its local answer, `None` if it is sent.
+pub fn check_batch_queries<Q>(
+    queries: impl IntoIterator<Item = Q>,
+    check: impl Fn(&str) -> Option<IpApiError>,
+) -> (Vec<String>, Vec<Option<IpApiError>>)
+where
+    Q: IntoQuery,
+{
+    queries
+        .into_iter()
+        .map(|query| match query.into_query() {
+            Ok(query) => {
+                let query = query.to_string();
+                let error = check(&query);
+                (query, error)
+            }
+            Err(error) => (query_text(&error), Some(error)),
+        })
+        .unzip()
+}
+
+/// Gets the text of a query.
+/// The text of an invalid query is kept, so the batch it is sent in rejects it again.
+///
+/// # Arguments
+/// * `query` - The query.
+///
+/// # Returns
+/// * `String` - The text of the query.
+pub fn query_to_string(query: impl IntoQuery) -> String {
+    match query.into_query() {
+        Ok(query) => query.to_string(),
+        Err(error) => query_text(&error),
+    }
+}
+
+/// Gets the text of the query an error is about.
+fn query_text(error: &IpApiError) -> String {
+    error.query().unwrap_or_default().to_string()
+}
+
+/// Merges the local answers of a batch with the results of the API.
+///
+/// # Arguments
+/// * `local` - The local answer of every query, `None` if the query was sent.
+/// * `results` - The results of the API for the sent queries, in their original order.
+///
+/// # Returns
+/// * `Vec<Result<T, ip-api4rs::error::IpApiError>>` - The result of every query, in the order of the batch.
+pub fn merge_batch_results<T>(
+    local: Vec<Option<IpApiError>>,
+    results: Vec<Result<T, IpApiError>>,
+) -> Vec<Result<T, IpApiError>> {
+    let mut results = results.into_iter();
+    local
+        .into_iter()
+        .map(|error| match error {
+            Some(error) => Err(error),
+            None => results.next().unwrap_or_else(|| Err(IpApiError::Unknown(MISSING_BATCH_RESULT.to_string()))),
+        })
+        .collect()
+}
+
+/// Fails every query of a batch whose request failed, so the results of the other batches are kept.
+///
+/// # Arguments
+/// * `queries` - The queries of the batch.
+/// * `error` - The error of the request, shared by the results.
+///
+/// # Returns
+/// * `Vec<Result<T, ip-api4rs::error::IpApiError>>` - An `IpApiError::BatchFailed` for every query.
+pub fn fail_batch<T>(queries: &[&str], error: IpApiError) -> Vec<Result<T, IpApiError>> {
+    let error = Arc::new(error);
+    queries
+        .iter()
+        .map(|query| {
+            Err(IpApiError::BatchFailed {
+                query: query.to_string(),
+                source: error.clone(),
+            })
+        })
+        .collect()
+}
+
+/// The message of the error returned when the API answered a batch with fewer results than queries.
+const MISSING_BATCH_RESULT: &str = "The API returned no result for the query.";
+
 /// Validates the result for errors.
 ///
 /// # Arguments
@@ -87,7 +545,13 @@ fn validate_result(json: String) -> Option<IpApiError> {
         return match serde_json::from_str::<ErrorResponse>(&json) {
             Ok(error_response) => match error_response.message.as_str() {
                 constant::ERROR_RESERVED_RANGE => Some(IpApiError::ReservedRange(error_response)),
+                constant::ERROR_PRIVATE_RANGE => Some(IpApiError::PrivateRange(error_response)),
                 constant::ERROR_INVALID_QUERY => Some(IpApiError::InvalidQuery(error_response)),
+                constant::ERROR_INVALID_KEY => Some(IpApiError::InvalidKey(error_response)),
+                constant::ERROR_QUOTA_EXCEEDED => Some(IpApiError::QuotaExceeded(error_response)),
+                message if message.starts_with(constant::ERROR_SSL_UNAVAILABLE) => {
+                    Some(IpApiError::SslUnavailable(error_response))
+                }
                 _ => Some(IpApiError::Unknown(json.clone())),
             },
             Err(err) => Some(IpApiError::JsonParseError(err)),
@@ -120,6 +584,21 @@ async fn wait_for_rate_limiter(limiter: &Option<DefaultDirectRateLimiter>) {
     }
 }
 
+/// Waits until the window of the API resets, if no requests are left in it.
+async fn wait_for_server_rate_limit(server_limit: &ServerRateLimit) {
+    if let Some(duration) = server_limit.time_until_ready() {
+        Delay::new(duration).await
+    }
+}
+
+/// Blocks until the window of the API resets, if no requests are left in it.
+#[cfg(feature = "blocking")]
+fn block_until_server_rate_limit(server_limit: &ServerRateLimit) {
+    if let Some(duration) = server_limit.time_until_ready() {
+        sleep(duration);
+    }
+}
+
 /// Blocks until the rate limiter is ready.
 #[cfg(feature = "blocking")]
 fn block_until_rate_limiter(limiter: &Option<DefaultDirectRateLimiter>) {
@@ -0,0 +1,186 @@
+//! A module that contains the fixtures the mock server answers with.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::constant;
+use crate::field::{Field, FieldSet};
+use crate::model::edns_response::{EdnsResponse, EdnsServer};
+
+/// The answer of the mock server for a single query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fixture {
+    /// A successful response with every field the mock can return for the query.
+    Success(Map<String, Value>),
+    /// A failed response with the given message, like `reserved range`.
+    Fail(String),
+    /// A response with a custom status code and body, like an error page of a proxy.
+    /// A batch that contains the query is answered with it as a whole, like a failed batch request.
+    Raw {
+        /// The status code of the response.
+        status: u16,
+        /// The body of the response.
+        body: String,
+    },
+}
+
+/// A table that maps queries to the answers of the mock server.
+/// Queries without a fixture are answered with `invalid query`, like the API does for unknown hosts.
+#[derive(Debug, Clone, Default)]
+pub struct Fixtures {
+    entries: HashMap<String, Fixture>,
+    edns: Option<EdnsResponse>,
+}
+
+impl Fixtures {
+    /// Creates a new empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a successful response for a query.
+    /// The `status` and `query` fields are added if the response does not contain them.
+    ///
+    /// # Arguments
+    /// * `query` - The query to answer.
+    /// * `response` - The response, serialized to a JSON object.
+    ///
+    /// # Panics
+    /// Panics if the response does not serialize to a JSON object.
+    pub fn success(mut self, query: impl Into<String>, response: impl Serialize) -> Self {
+        let response = match serde_json::to_value(response) {
+            Ok(Value::Object(response)) => response,
+            _ => panic!("The response of a fixture must serialize to a JSON object."),
+        };
+        self.entries.insert(query.into(), Fixture::Success(response));
+        self
+    }
+
+    /// Adds a failed response for a query.
+    ///
+    /// # Arguments
+    /// * `query` - The query to answer.
+    /// * `message` - The message of the failed response.
+    pub fn fail(mut self, query: impl Into<String>, message: impl Into<String>) -> Self {
+        self.entries.insert(query.into(), Fixture::Fail(message.into()));
+        self
+    }
+
+    /// Adds a response with a custom status code and body for a query.
+    ///
+    /// # Arguments
+    /// * `query` - The query to answer.
+    /// * `status` - The status code of the response.
+    /// * `body` - The body of the response.
+    pub fn raw(mut self, query: impl Into<String>, status: u16, body: impl Into<String>) -> Self {
+        self.entries.insert(
+            query.into(),
+            Fixture::Raw {
+                status,
+                body: body.into(),
+            },
+        );
+        self
+    }
+
+    /// Adds a `reserved range` response for a query.
+    ///
+    /// # Arguments
+    /// * `query` - The query to answer.
+    pub fn reserved_range(self, query: impl Into<String>) -> Self {
+        self.fail(query, constant::ERROR_RESERVED_RANGE)
+    }
+
+    /// Sets the response of the EDNS endpoint.
+    /// Without it the EDNS endpoint reports the address of the client as the resolver.
+    ///
+    /// # Arguments
+    /// * `response` - The response of the EDNS endpoint.
+    pub fn edns(mut self, response: EdnsResponse) -> Self {
+        self.edns = Some(response);
+        self
+    }
+
+    /// Renders the answer of the EDNS endpoint.
+    ///
+    /// # Arguments
+    /// * `peer` - The address of the client.
+    ///
+    /// # Returns
+    /// * `Value` - The JSON object of the answer.
+    pub fn render_edns(&self, peer: &str) -> Value {
+        let response = self.edns.clone().unwrap_or_else(|| EdnsResponse {
+            dns: EdnsServer {
+                ip: peer.to_string(),
+                geo: String::new(),
+            },
+            edns: None,
+        });
+        serde_json::to_value(response).unwrap()
+    }
+
+    /// Gets the fixture for a query.
+    ///
+    /// # Arguments
+    /// * `query` - The query.
+    ///
+    /// # Returns
+    /// * `Option<&Fixture>` - The fixture or `None` if the table has none for the query.
+    pub fn get(&self, query: &str) -> Option<&Fixture> {
+        self.entries.get(query)
+    }
+
+    /// Renders the answer for a query the way the API does.
+    ///
+    /// # Arguments
+    /// * `query` - The query.
+    /// * `fields` - The value of the `fields` parameter, `None` returns the default fields.
+    ///
+    /// # Returns
+    /// * `Value` - The JSON object of the answer.
+    pub fn render(&self, query: &str, fields: Option<&str>) -> Value {
+        let response = match self.get(query) {
+            Some(Fixture::Success(response)) => response,
+            Some(Fixture::Fail(message)) => return render_fail(query, message),
+            Some(Fixture::Raw {
+                ..
+            })
+            | None => return render_fail(query, constant::ERROR_INVALID_QUERY),
+        };
+        let fields = parse_fields(fields);
+        let mut rendered = Map::new();
+        for field in fields {
+            let value = match field {
+                "status" => Some(Value::from("success")),
+                "query" => Some(response.get("query").cloned().unwrap_or_else(|| Value::from(query))),
+                _ => response.get(field).cloned(),
+            };
+            if let Some(value) = value {
+                rendered.insert(field.to_string(), value);
+            }
+        }
+        Value::Object(rendered)
+    }
+}
+
+/// Renders a failed response.
+fn render_fail(query: &str, message: &str) -> Value {
+    serde_json::json!({
+        "status": "fail",
+        "message": message,
+        "query": query,
+    })
+}
+
+/// Parses the `fields` parameter, which is either a numeric mask or a comma separated list of names.
+fn parse_fields(fields: Option<&str>) -> Vec<&str> {
+    match fields {
+        None => Field::DEFAULT.iter().map(Field::name).collect(),
+        Some(fields) => match fields.parse::<u32>() {
+            Ok(bits) => FieldSet::from_bits(bits).fields().iter().map(Field::name).collect(),
+            Err(_) => fields.split(',').collect(),
+        },
+    }
+}
@@ -0,0 +1,530 @@
+//! A module that contains a client that answers from a programmable table instead of the API.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+#[cfg(feature = "blocking")]
+use std::sync::OnceLock;
+use std::sync::{Arc, Mutex};
+
+use governor::DefaultDirectRateLimiter;
+#[cfg(feature = "blocking")]
+use reqwest::blocking;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+#[cfg(feature = "blocking")]
+use crate::client::BlockingIpApi;
+use crate::client::{AsyncIpApi, IpApi};
+use crate::constant;
+use crate::error::IpApiError;
+use crate::field::{Field, FieldSet};
+use crate::format::{self, ResponseFormat};
+use crate::language::Language;
+use crate::model::edns_response::EdnsResponse;
+use crate::model::ip_response::{ErrorResponse, IpDefaultResponse, IpFullResponse, IpResponse};
+use crate::query::{IntoQuery, Query};
+use crate::request_handler;
+use crate::util::rate_limit::{RateLimitHandling, ServerRateLimit};
+use crate::util::retry::RetryPolicy;
+
+/// A factory for an error the mock answers with.
+/// The errors of the library can not be cloned, so a new one is created for every call.
+type ErrorFactory = Arc<dyn Fn() -> IpApiError + Send + Sync>;
+
+/// The answer of the mock for a query.
+#[derive(Clone)]
+enum MockAnswer {
+    /// The raw JSON of a response, parsed like a response of the API.
+    Json(String),
+    /// An error that is returned as is.
+    Error(ErrorFactory),
+}
+
+/// The methods of the clients that can be called on the mock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockMethod {
+    /// `query_api_default`
+    Default,
+    /// `query_api_fully`
+    Fully,
+    /// `query_api`
+    Custom,
+    /// `query_api_localized`
+    Localized,
+    /// `query_fields`, answered with the requested fields of the response for the query.
+    Fields,
+    /// `query_api_raw`, answered with the response for the query, encoded in the requested format.
+    Raw,
+    /// `query_self`, answered with the response for the empty query.
+    SelfIp,
+    /// `query_edns`, answered with the response set through `MockIpApiClient::edns`.
+    Edns,
+    /// `query_api_batch`
+    Batch,
+    /// `query_api_bulk`
+    Bulk,
+}
+
+/// A call the mock received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockCall {
+    /// The method that was called.
+    pub method: MockMethod,
+    /// The queries of the call, a single one unless the method is `Batch` or `Bulk`.
+    pub queries: Vec<String>,
+    /// The language passed to `query_api_localized`.
+    pub language: Option<Language>,
+}
+
+/// A client that implements `AsyncIpApi` and `BlockingIpApi` without sending any request.
+///
+/// Every query is answered from a table of stored responses, which are deserialized into the requested type,
+/// so custom models work the same way they do with the real clients.
+/// Queries without an answer fail with `IpApiError::InvalidQuery`, like the API does for unknown hosts.
+/// The calls are recorded, so tests can assert on them.
+pub struct MockIpApiClient {
+    answers: HashMap<String, MockAnswer>,
+    calls: Mutex<Vec<MockCall>>,
+    client: Client,
+    #[cfg(feature = "blocking")]
+    blocking_client: OnceLock<blocking::Client>,
+    limiter: Option<DefaultDirectRateLimiter>,
+    server_limit: ServerRateLimit,
+    retry_policy: RetryPolicy,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    edns: Option<String>,
+    language: Option<Language>,
+}
+
+impl Default for MockIpApiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for MockIpApiClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockIpApiClient")
+            .field("queries", &self.answers.keys().collect::<Vec<_>>())
+            .field("calls", &self.calls)
+            .finish()
+    }
+}
+
+impl MockIpApiClient {
+    /// Creates a new MockIpApiClient without any answers.
+    pub fn new() -> Self {
+        Self {
+            answers: HashMap::new(),
+            calls: Mutex::new(Vec::new()),
+            client: Client::new(),
+            #[cfg(feature = "blocking")]
+            blocking_client: OnceLock::new(),
+            limiter: None,
+            server_limit: ServerRateLimit::new(),
+            retry_policy: RetryPolicy::none(),
+            api_key: None,
+            base_url: None,
+            edns: None,
+            language: None,
+        }
+    }
+
+    /// Adds a successful response for a query.
+    /// The `status` and `query` fields are added if the response does not contain them.
+    ///
+    /// # Arguments
+    /// * `query` - The query to answer.
+    /// * `response` - The response, like an `IpFullResponse` or a `serde_json::Value`.
+    ///
+    /// # Panics
+    /// Panics if the response does not serialize to a JSON object.
+    pub fn response(mut self, query: impl Into<String>, response: impl Serialize) -> Self {
+        let query = query.into();
+        let mut response = match serde_json::to_value(response) {
+            Ok(Value::Object(response)) => response,
+            _ => panic!("The response of a mock must serialize to a JSON object."),
+        };
+        response.entry("status").or_insert_with(|| Value::from("success"));
+        response.entry("query").or_insert_with(|| Value::from(query.as_str()));
+        self.answers.insert(query, MockAnswer::Json(Value::Object(response).to_string()));
+        self
+    }
+
+    /// Adds a failed response for a query, which is turned into an error like a failed response of the API.
+    ///
+    /// # Arguments
+    /// * `query` - The query to answer.
+    /// * `message` - The message of the failed response, like `reserved range`.
+    pub fn fail(mut self, query: impl Into<String>, message: impl Into<String>) -> Self {
+        let query = query.into();
+        let json = build_fail_json(&query, &message.into());
+        self.answers.insert(query, MockAnswer::Json(json));
+        self
+    }
+
+    /// Adds an error for a query, like `IpApiError::RateLimited`.
+    ///
+    /// # Arguments
+    /// * `query` - The query to answer.
+    /// * `error` - Creates the error for every call.
+    pub fn error(mut self, query: impl Into<String>, error: impl Fn() -> IpApiError + Send + Sync + 'static) -> Self {
+        self.answers.insert(query.into(), MockAnswer::Error(Arc::new(error)));
+        self
+    }
+
+    /// Sets the response of the EDNS endpoint.
+    /// Without it `query_edns` fails with `IpApiError::Unknown`.
+    ///
+    /// # Arguments
+    /// * `response` - The response of the EDNS endpoint.
+    pub fn edns(mut self, response: EdnsResponse) -> Self {
+        self.edns = Some(serde_json::to_string(&response).unwrap());
+        self
+    }
+
+    /// Sets the default language the mock reports through `IpApi::get_language`.
+    ///
+    /// # Arguments
+    /// * `language` - The default language.
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// Gets the calls the mock received, in the order they were made.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Gets the amount of calls the mock received.
+    pub fn call_count(&self) -> usize {
+        self.calls.lock().unwrap().len()
+    }
+
+    /// Forgets the recorded calls.
+    pub fn clear_calls(&self) {
+        self.calls.lock().unwrap().clear()
+    }
+
+    /// Records a call.
+    fn record(&self, method: MockMethod, queries: Vec<String>, language: Option<Language>) {
+        self.calls.lock().unwrap().push(MockCall {
+            method,
+            queries,
+            language,
+        });
+    }
+
+    /// Answers a single query.
+    fn answer<T>(&self, query: &str) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        match self.answers.get(query) {
+            Some(MockAnswer::Json(json)) => request_handler::process_result::<T>(json.clone()),
+            Some(MockAnswer::Error(error)) => Err(error()),
+            None => request_handler::process_result::<T>(build_fail_json(query, constant::ERROR_INVALID_QUERY)),
+        }
+    }
+
+    /// Answers the queries of a batch.
+    /// Queries with a local answer, like invalid queries, are rejected like the real clients do.
+    fn answer_batch<T>(&self, queries: &[String], local: Vec<Option<IpApiError>>) -> Vec<Result<T, IpApiError>>
+    where
+        T: DeserializeOwned,
+    {
+        queries
+            .iter()
+            .zip(local)
+            .map(|(query, error)| match error {
+                Some(error) => Err(error),
+                None => self.answer::<T>(query),
+            })
+            .collect()
+    }
+
+    /// Records and answers a single query.
+    /// Invalid queries are rejected like the real clients do.
+    fn query<T>(&self, method: MockMethod, ip: impl IntoQuery, language: Option<Language>) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        let query = ip.into_query();
+        let ip = match &query {
+            Ok(query) => query.to_string(),
+            Err(error) => error.query().unwrap_or_default().to_string(),
+        };
+        self.record(method, vec![ip.clone()], language);
+        query?;
+        self.answer::<T>(&ip)
+    }
+
+    /// Records and answers a single query with the given fields of the stored response, like the API does.
+    fn query_columns(
+        &self,
+        method: MockMethod,
+        ip: impl IntoQuery,
+        columns: &[Field],
+    ) -> Result<Map<String, Value>, IpApiError> {
+        let query = ip.into_query();
+        let ip = match &query {
+            Ok(query) => query.to_string(),
+            Err(error) => error.query().unwrap_or_default().to_string(),
+        };
+        self.record(method, vec![ip.clone()], None);
+        query?;
+        let json = match self.answers.get(&ip) {
+            Some(MockAnswer::Json(json)) => json.clone(),
+            Some(MockAnswer::Error(error)) => return Err(error()),
+            None => build_fail_json(&ip, constant::ERROR_INVALID_QUERY),
+        };
+        let mut object = request_handler::process_result::<Map<String, Value>>(json)?;
+        object.retain(|name, _| columns.iter().any(|field| field.name() == name));
+        Ok(object)
+    }
+
+    /// Records and answers a query of the EDNS endpoint.
+    fn answer_edns(&self) -> Result<EdnsResponse, IpApiError> {
+        self.record(MockMethod::Edns, Vec::new(), None);
+        match &self.edns {
+            Some(json) => request_handler::process_result::<EdnsResponse>(json.clone()),
+            None => Err(IpApiError::Unknown(MISSING_EDNS_RESPONSE.to_string())),
+        }
+    }
+
+    /// Records and answers a batch of queries.
+    fn query_batch<T>(&self, queries: &[impl IntoQuery + Clone]) -> Result<Vec<Result<T, IpApiError>>, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        let (queries, local) = request_handler::check_batch_queries(queries.iter().cloned(), |_| None);
+        self.record(MockMethod::Batch, queries.clone(), None);
+        if queries.len() > constant::BATCH_SIZE_LIMIT {
+            return Err(IpApiError::BatchTooLarge(queries.len()));
+        }
+        Ok(self.answer_batch::<T>(&queries, local))
+    }
+
+    /// Records and answers any amount of queries.
+    fn query_bulk<T, I>(&self, queries: I) -> Result<Vec<Result<T, IpApiError>>, IpApiError>
+    where
+        T: DeserializeOwned,
+        I: IntoIterator,
+        I::Item: IntoQuery,
+    {
+        let (queries, local) = request_handler::check_batch_queries(queries, |_| None);
+        self.record(MockMethod::Bulk, queries.clone(), None);
+        Ok(self.answer_batch::<T>(&queries, local))
+    }
+}
+
+/// The message of the error returned when the mock has no response for the EDNS endpoint.
+const MISSING_EDNS_RESPONSE: &str = "The mock has no response for the EDNS endpoint.";
+
+/// Builds the JSON of a failed response.
+fn build_fail_json(query: &str, message: &str) -> String {
+    let response = ErrorResponse {
+        status: "fail".to_string(),
+        message: message.to_string(),
+        query: query.to_string(),
+    };
+    serde_json::to_string(&response).unwrap()
+}
+
+impl IpApi for MockIpApiClient {
+    fn get_api_key(&self) -> &Option<String> {
+        &self.api_key
+    }
+
+    fn get_base_url(&self) -> &Option<String> {
+        &self.base_url
+    }
+
+    fn get_edns_base_url(&self) -> &Option<String> {
+        &None
+    }
+
+    fn get_response_format(&self) -> ResponseFormat {
+        ResponseFormat::Json
+    }
+
+    fn get_language(&self) -> &Option<Language> {
+        &self.language
+    }
+
+    fn get_rate_limiter(&self) -> &Option<DefaultDirectRateLimiter> {
+        &self.limiter
+    }
+
+    fn get_batch_rate_limiter(&self) -> &Option<DefaultDirectRateLimiter> {
+        &self.limiter
+    }
+
+    fn get_server_rate_limit(&self) -> &ServerRateLimit {
+        &self.server_limit
+    }
+
+    fn get_batch_server_rate_limit(&self) -> &ServerRateLimit {
+        &self.server_limit
+    }
+
+    fn get_rate_limit_handling(&self) -> RateLimitHandling {
+        RateLimitHandling::default()
+    }
+
+    fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    fn get_reserved_range_check(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl BlockingIpApi for MockIpApiClient {
+    fn query_api_default(&self, ip: impl IntoQuery) -> Result<IpDefaultResponse, IpApiError> {
+        self.query::<IpDefaultResponse>(MockMethod::Default, ip, None)
+    }
+
+    fn query_api_fully(&self, ip: impl IntoQuery) -> Result<IpFullResponse, IpApiError> {
+        self.query::<IpFullResponse>(MockMethod::Fully, ip, None)
+    }
+
+    fn query_api<T>(&self, ip: impl IntoQuery) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.query::<T>(MockMethod::Custom, ip, None)
+    }
+
+    fn query_api_localized<T>(&self, ip: impl IntoQuery, language: Language) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.query::<T>(MockMethod::Localized, ip, Some(language))
+    }
+
+    fn query_fields(&self, ip: impl IntoQuery, fields: &[Field]) -> Result<IpResponse, IpApiError> {
+        let object = self.query_columns(MockMethod::Fields, ip, &FieldSet::from_fields_or_default(fields).fields())?;
+        Ok(serde_json::from_value(Value::Object(object))?)
+    }
+
+    fn query_api_raw<T>(&self, ip: impl IntoQuery, format: ResponseFormat) -> Result<String, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        let object = self.query_columns(MockMethod::Raw, ip, &format::columns::<T>())?;
+        Ok(format::encode(format, &object))
+    }
+
+    fn query_self<T>(&self) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.query::<T>(MockMethod::SelfIp, Query::SelfIp, None)
+    }
+
+    fn query_edns(&self) -> Result<EdnsResponse, IpApiError> {
+        self.answer_edns()
+    }
+
+    fn query_api_batch<T>(&self, queries: &[impl IntoQuery + Clone]) -> Result<Vec<Result<T, IpApiError>>, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.query_batch::<T>(queries)
+    }
+
+    fn query_api_bulk<T, I>(&self, queries: I) -> Result<Vec<Result<T, IpApiError>>, IpApiError>
+    where
+        T: DeserializeOwned,
+        I: IntoIterator,
+        I::Item: IntoQuery,
+    {
+        self.query_bulk::<T, I>(queries)
+    }
+
+    fn get_http_client(&self) -> &blocking::Client {
+        self.blocking_client.get_or_init(blocking::Client::new)
+    }
+}
+
+impl AsyncIpApi for MockIpApiClient {
+    async fn query_api_default(&self, ip: impl IntoQuery + Send) -> Result<IpDefaultResponse, IpApiError> {
+        self.query::<IpDefaultResponse>(MockMethod::Default, ip, None)
+    }
+
+    async fn query_api_fully(&self, ip: impl IntoQuery + Send) -> Result<IpFullResponse, IpApiError> {
+        self.query::<IpFullResponse>(MockMethod::Fully, ip, None)
+    }
+
+    async fn query_api<T>(&self, ip: impl IntoQuery + Send) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.query::<T>(MockMethod::Custom, ip, None)
+    }
+
+    async fn query_api_localized<T>(&self, ip: impl IntoQuery + Send, language: Language) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.query::<T>(MockMethod::Localized, ip, Some(language))
+    }
+
+    async fn query_fields(&self, ip: impl IntoQuery + Send, fields: &[Field]) -> Result<IpResponse, IpApiError> {
+        let object = self.query_columns(MockMethod::Fields, ip, &FieldSet::from_fields_or_default(fields).fields())?;
+        Ok(serde_json::from_value(Value::Object(object))?)
+    }
+
+    async fn query_api_raw<T>(&self, ip: impl IntoQuery + Send, format: ResponseFormat) -> Result<String, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        let object = self.query_columns(MockMethod::Raw, ip, &format::columns::<T>())?;
+        Ok(format::encode(format, &object))
+    }
+
+    async fn query_self<T>(&self) -> Result<T, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.query::<T>(MockMethod::SelfIp, Query::SelfIp, None)
+    }
+
+    async fn query_edns(&self) -> Result<EdnsResponse, IpApiError> {
+        self.answer_edns()
+    }
+
+    async fn query_api_batch<T>(
+        &self,
+        queries: &[impl IntoQuery + Clone + Sync],
+    ) -> Result<Vec<Result<T, IpApiError>>, IpApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.query_batch::<T>(queries)
+    }
+
+    fn query_api_bulk<T, I>(
+        &self,
+        queries: I,
+    ) -> impl Future<Output = Result<Vec<Result<T, IpApiError>>, IpApiError>> + Send
+    where
+        T: DeserializeOwned + Send,
+        I: IntoIterator,
+        I::Item: IntoQuery,
+    {
+        std::future::ready(self.query_bulk::<T, I>(queries))
+    }
+
+    fn get_http_client(&self) -> &Client {
+        &self.client
+    }
+}
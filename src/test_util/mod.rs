@@ -0,0 +1,25 @@
+//! A module that contains an in-process mock of the ip-api.com API for offline testing.
+//!
+//! The mock serves `/json/{query}` and `/batch` from a table of fixtures and mimics the rate limit of the API.
+//! Combined with a custom base url, the clients can be tested without network access.
+//! Code that only depends on the client traits can use the `MockIpApiClient` instead, which sends no requests at all.
+//!
+//! # Example
+//! ```
+//! use ip_api4rs::client::BlockingIpApi;
+//! use ip_api4rs::blocking::client::BlockingIpApiClient;
+//! use ip_api4rs::error::IpApiError;
+//! use ip_api4rs::model::ip_response::IpDefaultResponse;
+//! use ip_api4rs::test_util::fixture::Fixtures;
+//! use ip_api4rs::test_util::server::MockServer;
+//!
+//! let server = MockServer::start(Fixtures::new().fail("8.8.8.8", "quota exceeded")).unwrap();
+//! let client = BlockingIpApiClient::builder().base_url(server.base_url()).build_blocking().unwrap();
+//! let result = client.query_api::<IpDefaultResponse>("8.8.8.8");
+//! assert!(matches!(result, Err(IpApiError::QuotaExceeded(_))));
+//! assert_eq!(server.request_count(), 1);
+//! ```
+
+pub mod fixture;
+pub mod mock_client;
+pub mod server;
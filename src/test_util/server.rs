@@ -0,0 +1,352 @@
+//! A module that contains the mock server.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::constant;
+use crate::format::{self, ResponseFormat};
+use crate::test_util::fixture::{Fixture, Fixtures};
+use crate::util::urls::percent_decode;
+
+/// A mock of the ip-api.com API that runs in a background thread of the current process.
+///
+/// Single requests are answered in the format of their path, like `/csv/8.8.8.8`.
+/// Single and batch requests have their own rate limit window, like on the free tier of the API.
+/// Every response reports the window through the `X-Rl` and `X-Ttl` headers.
+/// Requests beyond the limit are answered with HTTP 429.
+/// The EDNS endpoint is served under `/edns/json`, without a rate limit.
+///
+/// The server shuts down when it is dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<MockServerState>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// The state shared between the server and its connections.
+#[derive(Debug)]
+struct MockServerState {
+    fixtures: Fixtures,
+    window: Duration,
+    limits: [u32; 2],
+    windows: Mutex<[Window; 2]>,
+    requests: Mutex<Vec<String>>,
+    shutdown: AtomicBool,
+}
+
+/// A rate limit window of the mock server.
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    started_at: Instant,
+    used: u32,
+}
+
+/// The endpoints of the mock server.
+#[derive(Debug, Clone, Copy)]
+enum Endpoint {
+    Single = 0,
+    Batch = 1,
+}
+
+/// A response of the mock server.
+struct MockResponse {
+    status: String,
+    body: String,
+    rate_limit: Option<(u32, u64)>,
+}
+
+impl MockServer {
+    /// Starts a new mock server with the rate limits of the free tier.
+    ///
+    /// # Arguments
+    /// * `fixtures` - The fixtures to answer with.
+    ///
+    /// # Returns
+    /// * `io::Result<MockServer>` - The running server or the error that occurred while binding it.
+    pub fn start(fixtures: Fixtures) -> io::Result<Self> {
+        Self::start_with_rate_limit(fixtures, 45, 15, Duration::from_secs(60))
+    }
+
+    /// Starts a new mock server with custom rate limits.
+    ///
+    /// # Arguments
+    /// * `fixtures` - The fixtures to answer with.
+    /// * `requests` - The amount of single requests per window.
+    /// * `batch_requests` - The amount of batch requests per window.
+    /// * `window` - The length of a window.
+    ///
+    /// # Returns
+    /// * `io::Result<MockServer>` - The running server or the error that occurred while binding it.
+    pub fn start_with_rate_limit(
+        fixtures: Fixtures,
+        requests: u32,
+        batch_requests: u32,
+        window: Duration,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        let now = Instant::now();
+        let state = Arc::new(MockServerState {
+            fixtures,
+            window,
+            limits: [requests, batch_requests],
+            windows: Mutex::new(
+                [Window {
+                    started_at: now,
+                    used: 0,
+                }; 2],
+            ),
+            requests: Mutex::new(Vec::new()),
+            shutdown: AtomicBool::new(false),
+        });
+        let server_state = state.clone();
+        let handle = thread::spawn(move || accept_connections(listener, server_state));
+        Ok(Self {
+            address,
+            state,
+            handle: Some(handle),
+        })
+    }
+
+    /// Gets the base url of the server, to be passed to the builder of a client.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Gets the base url of the EDNS endpoint of the server, to be passed to the builder of a client.
+    pub fn edns_base_url(&self) -> String {
+        format!("http://{}/edns", self.address)
+    }
+
+    /// Gets the amount of requests the server received.
+    pub fn request_count(&self) -> usize {
+        self.state.requests.lock().unwrap().len()
+    }
+
+    /// Gets the method and target of every request the server received, like `GET /json/8.8.8.8?fields=61439`.
+    pub fn requests(&self) -> Vec<String> {
+        self.state.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.state.shutdown.store(true, Ordering::SeqCst);
+        let _ = TcpStream::connect(self.address);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl MockServerState {
+    /// Counts a request against the window of an endpoint.
+    ///
+    /// # Returns
+    /// * `Result<(u32, u64), u64>` - The requests left and the seconds until the window resets,
+    ///   or only the seconds until the window resets if the limit is exceeded.
+    fn acquire(&self, endpoint: Endpoint) -> Result<(u32, u64), u64> {
+        let mut windows = self.windows.lock().unwrap();
+        let window = &mut windows[endpoint as usize];
+        if window.started_at.elapsed() >= self.window {
+            window.started_at = Instant::now();
+            window.used = 0;
+        }
+        let ttl = self.window.saturating_sub(window.started_at.elapsed()).as_secs_f64().ceil().max(1.0) as u64;
+        let limit = self.limits[endpoint as usize];
+        if window.used >= limit {
+            return Err(ttl);
+        }
+        window.used += 1;
+        Ok((limit - window.used, ttl))
+    }
+}
+
+impl MockResponse {
+    /// Creates a new response without rate limit headers.
+    fn new(status: &str, body: String) -> Self {
+        Self {
+            status: status.to_string(),
+            body,
+            rate_limit: None,
+        }
+    }
+}
+
+/// Accepts connections until the server shuts down.
+fn accept_connections(listener: TcpListener, state: Arc<MockServerState>) {
+    for stream in listener.incoming() {
+        if state.shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        if let Ok(stream) = stream {
+            let state = state.clone();
+            thread::spawn(move || {
+                let _ = handle_connection(stream, &state);
+            });
+        }
+    }
+}
+
+/// Reads a single request from a connection and answers it.
+fn handle_connection(mut stream: TcpStream, state: &MockServerState) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    state.requests.lock().unwrap().push(format!("{} {}", method, target));
+    let peer = stream.peer_addr()?.ip().to_string();
+    let response = route(state, method, target, &body, &peer);
+    write_response(&mut stream, response)
+}
+
+/// Answers a request.
+fn route(state: &MockServerState, method: &str, target: &str, body: &[u8], peer: &str) -> MockResponse {
+    let (path, query_string) = target.split_once('?').unwrap_or((target, ""));
+    let params = parse_params(query_string);
+    let fields = params.get("fields").map(String::as_str);
+    if (method, path) == ("GET", "/edns/json") {
+        return MockResponse::new("200 OK", state.fixtures.render_edns(peer).to_string());
+    }
+    let endpoint = match (method, path) {
+        ("GET", path) if path_format(path).is_some() => Endpoint::Single,
+        ("POST", "/batch") => Endpoint::Batch,
+        _ => return MockResponse::new("404 Not Found", String::new()),
+    };
+    let rate_limit = match state.acquire(endpoint) {
+        Ok(rate_limit) => rate_limit,
+        Err(ttl) => {
+            return MockResponse {
+                status: "429 Too Many Requests".to_string(),
+                body: String::new(),
+                rate_limit: Some((0, ttl)),
+            }
+        }
+    };
+    let body = match endpoint {
+        Endpoint::Single => {
+            let format = path_format(path).unwrap_or_default();
+            let query =
+                percent_decode(path[1..].strip_prefix(format.path()).unwrap_or_default().trim_start_matches('/'));
+            let query = if query.is_empty() {
+                peer.to_string()
+            } else {
+                query
+            };
+            if let Some(Fixture::Raw {
+                status,
+                body,
+            }) = state.fixtures.get(&query)
+            {
+                return MockResponse {
+                    status: format!("{} Mock", status),
+                    body: body.clone(),
+                    rate_limit: Some(rate_limit),
+                };
+            }
+            match state.fixtures.render(&query, fields) {
+                Value::Object(object) => format::encode(format, &object),
+                rendered => rendered.to_string(),
+            }
+        }
+        Endpoint::Batch => match serde_json::from_slice::<Vec<Value>>(body) {
+            Ok(entries) => {
+                let raw = entries.iter().find_map(|entry| match state.fixtures.get(batch_entry_query(entry)) {
+                    Some(Fixture::Raw {
+                        status,
+                        body,
+                    }) => Some((status, body)),
+                    _ => None,
+                });
+                if let Some((status, body)) = raw {
+                    return MockResponse {
+                        status: format!("{} Mock", status),
+                        body: body.clone(),
+                        rate_limit: Some(rate_limit),
+                    };
+                }
+                Value::Array(entries.iter().map(|entry| render_batch_entry(&state.fixtures, entry, fields)).collect())
+                    .to_string()
+            }
+            Err(_) => return MockResponse::new("400 Bad Request", String::new()),
+        },
+    };
+    MockResponse {
+        status: "200 OK".to_string(),
+        body,
+        rate_limit: Some(rate_limit),
+    }
+}
+
+/// Gets the response format of a single request from its path, like `csv` from `/csv/8.8.8.8`.
+fn path_format(path: &str) -> Option<ResponseFormat> {
+    let segment = path.trim_start_matches('/').split('/').next()?;
+    ResponseFormat::from_path(segment)
+}
+
+/// Renders an entry of a batch, which is either a query or an object with its own `query` and `fields`.
+fn render_batch_entry(fixtures: &Fixtures, entry: &Value, fields: Option<&str>) -> Value {
+    let fields = entry.get("fields").and_then(Value::as_str).or(fields);
+    fixtures.render(batch_entry_query(entry), fields)
+}
+
+/// Gets the query of a batch entry.
+fn batch_entry_query(entry: &Value) -> &str {
+    match entry {
+        Value::String(query) => query,
+        Value::Object(entry) => entry.get("query").and_then(Value::as_str).unwrap_or_default(),
+        _ => "",
+    }
+}
+
+/// Writes a response to a connection.
+fn write_response(stream: &mut TcpStream, response: MockResponse) -> io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    if let Some((requests_left, ttl)) = response.rate_limit {
+        head.push_str(&format!("{}: {}\r\n", constant::HEADER_REQUESTS_LEFT, requests_left));
+        head.push_str(&format!("{}: {}\r\n", constant::HEADER_TIME_TO_RESET, ttl));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(response.body.as_bytes())?;
+    stream.flush()
+}
+
+/// Parses the parameters of a query string.
+fn parse_params(query_string: &str) -> HashMap<String, String> {
+    query_string
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+        .map(|(name, value)| (percent_decode(name), percent_decode(value)))
+        .collect()
+}
@@ -0,0 +1,26 @@
+//! A module that contains deserializers for the fields of the models.
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+/// Deserializes an optional field, an empty string or `null` becomes `None`.
+/// The API sends an empty string for fields it has no data for, like the district of most IP addresses.
+/// Combine it with `#[serde(default)]`, so a missing field becomes `None` as well.
+///
+/// # Arguments
+/// * `deserializer` - The deserializer of the field.
+///
+/// # Returns
+/// * `Result<Option<T>, D::Error>` - The value or `None` if the field is empty.
+pub fn empty_string_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::Null => Ok(None),
+        Value::String(text) if text.is_empty() => Ok(None),
+        value => T::deserialize(value).map(Some).map_err(D::Error::custom),
+    }
+}
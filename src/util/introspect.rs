@@ -0,0 +1,392 @@
+//! A module that extracts the names of the fields a struct is deserialized from.
+//!
+//! A plain struct reports its fields to the deserializer, which is all `serde_introspect` relies on.
+//! A struct with `#[serde(flatten)]` fields is deserialized from a map instead and the flattened structs never
+//! report theirs, so their fields are found by probing: the struct is deserialized from maps of dummy values
+//! and every name it misses or reads is one of its fields.
+//! A flattened `Option` turns the errors of its fields into `None`, so the errors are also recorded on the way.
+
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Formatter};
+
+use serde::de::value::StrDeserializer;
+use serde::de::{DeserializeSeed, Expected, MapAccess, SeqAccess, Unexpected, Visitor};
+use serde::{forward_to_deserialize_any, Deserialize, Deserializer};
+
+use crate::field::Field;
+
+thread_local! {
+    /// The last error raised while probing, even if the struct swallowed it.
+    static LAST_ERROR: Cell<Option<ProbeError>> = const { Cell::new(None) };
+}
+
+/// Gets the names of the fields a struct is deserialized from, including the fields of flattened structs.
+/// Names the struct only reads with a type no dummy value satisfies are left out,
+/// as are the fields of flattened structs after a field no dummy value satisfies.
+/// Strings, numbers, booleans, IP addresses and unit enum variants are satisfied.
+///
+/// # Returns
+/// * `Vec<&'static str>` - The names of the fields.
+pub fn struct_fields<'de, T>() -> Vec<&'static str>
+where
+    T: Deserialize<'de>,
+{
+    let read = RefCell::new(Vec::new());
+    let mut entries: Vec<(&'static str, Dummy)> = Vec::new();
+    // Every missing field is added with the first dummy value its type accepts, until the struct is complete.
+    let complete = loop {
+        let (result, swallowed) = probe::<T>(&entries, &read);
+        // A flattened `Option` turns a failure into `None`, its error is handled like one of the struct.
+        let error = match (result, swallowed) {
+            (Ok(()), None) => break true,
+            (Ok(()), Some(error)) | (Err(error), _) => error,
+        };
+        match error {
+            ProbeError::Fields(fields) => return fields.to_vec(),
+            ProbeError::Missing(name) if !entries.iter().any(|(entry, _)| *entry == name) => {
+                entries.push((name, Dummy::CANDIDATES[0]))
+            }
+            ProbeError::Variant(variant) => match entries.last_mut() {
+                Some(entry) if entry.1 != Dummy::Variant(variant) => entry.1 = Dummy::Variant(variant),
+                _ => break result.is_ok(),
+            },
+            ProbeError::Invalid | ProbeError::Other => match entries.last_mut().and_then(Dummy::next) {
+                Some(()) => continue,
+                None => break result.is_ok(),
+            },
+            ProbeError::Missing(_) => break result.is_ok(),
+        }
+    };
+
+    let mut names: Vec<&'static str> = entries.iter().map(|(name, _)| *name).collect();
+    if complete {
+        let (_, baseline) = probe::<T>(&entries, &read);
+        // A field with a default is never missing, but it rejects a value of the wrong type.
+        for field in Field::ALL {
+            if names.contains(&field.name()) {
+                continue;
+            }
+            let mut poisoned = entries.clone();
+            poisoned.push((field.name(), Dummy::Seq));
+            let (result, swallowed) = probe::<T>(&poisoned, &read);
+            if result.is_err() || (baseline.is_none() && swallowed.is_some()) {
+                names.push(field.name());
+            }
+        }
+    }
+    for name in read.into_inner() {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Deserializes a struct from a map of dummy values.
+///
+/// # Returns
+/// * `(Result<(), ProbeError>, Option<ProbeError>)` - The outcome and the last error raised on the way.
+fn probe<'de, T>(
+    entries: &[(&'static str, Dummy)],
+    read: &RefCell<Vec<&'static str>>,
+) -> (Result<(), ProbeError>, Option<ProbeError>)
+where
+    T: Deserialize<'de>,
+{
+    LAST_ERROR.with(|last| last.set(None));
+    let result = T::deserialize(Probe {
+        entries,
+        read,
+    })
+    .map(drop);
+    (result, LAST_ERROR.with(Cell::take))
+}
+
+/// A dummy value of a field, handed out when the struct does not tell which type it expects.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dummy {
+    Str(&'static str),
+    Float,
+    Int,
+    Bool,
+    Unit,
+    /// A variant of the enum the field is deserialized into.
+    Variant(&'static str),
+    /// An empty sequence, which the types of the API fields reject.
+    Seq,
+}
+
+impl Dummy {
+    /// The dummy values tried for a missing field, in order.
+    const CANDIDATES: [Dummy; 7] =
+        [Dummy::Str(""), Dummy::Float, Dummy::Int, Dummy::Bool, Dummy::Unit, Dummy::Str("0.0.0.0"), Dummy::Str("::")];
+
+    /// Replaces a dummy value with the next candidate, if there is one.
+    fn next(dummy: &mut (&'static str, Dummy)) -> Option<()> {
+        let index = Dummy::CANDIDATES.iter().position(|candidate| *candidate == dummy.1)?;
+        dummy.1 = *Dummy::CANDIDATES.get(index + 1)?;
+        Some(())
+    }
+}
+
+/// The outcome of a failed probe.
+#[derive(Debug, Clone, Copy)]
+enum ProbeError {
+    /// The struct reported its fields.
+    Fields(&'static [&'static str]),
+    /// The struct misses a field.
+    Missing(&'static str),
+    /// An enum expects one of its variants, like the first one.
+    Variant(&'static str),
+    /// A value has the wrong type.
+    Invalid,
+    /// Any other error.
+    Other,
+}
+
+impl Display for ProbeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ProbeError {}
+
+impl ProbeError {
+    /// Records the error as the last one raised while probing.
+    fn raise(self) -> Self {
+        LAST_ERROR.with(|last| last.set(Some(self)));
+        self
+    }
+}
+
+impl serde::de::Error for ProbeError {
+    fn custom<M: Display>(_msg: M) -> Self {
+        ProbeError::Other.raise()
+    }
+
+    fn invalid_type(_unexp: Unexpected, _exp: &dyn Expected) -> Self {
+        ProbeError::Invalid.raise()
+    }
+
+    fn invalid_value(_unexp: Unexpected, _exp: &dyn Expected) -> Self {
+        ProbeError::Invalid.raise()
+    }
+
+    fn invalid_length(_len: usize, _exp: &dyn Expected) -> Self {
+        ProbeError::Invalid.raise()
+    }
+
+    fn unknown_variant(_variant: &str, expected: &'static [&'static str]) -> Self {
+        match expected.first() {
+            Some(variant) => ProbeError::Variant(variant).raise(),
+            None => ProbeError::Invalid.raise(),
+        }
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        ProbeError::Missing(field).raise()
+    }
+}
+
+/// The deserializer of the probed struct.
+struct Probe<'a> {
+    entries: &'a [(&'static str, Dummy)],
+    read: &'a RefCell<Vec<&'static str>>,
+}
+
+impl<'de> Deserializer<'de> for Probe<'_> {
+    type Error = ProbeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_map(ProbeMap {
+            entries: self.entries.iter(),
+            value: None,
+            read: self.read,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        Err(ProbeError::Fields(fields))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit unit_struct
+        newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// The entries of the map a struct with flattened fields is deserialized from.
+struct ProbeMap<'a> {
+    entries: std::slice::Iter<'a, (&'static str, Dummy)>,
+    value: Option<(&'static str, Dummy)>,
+    read: &'a RefCell<Vec<&'static str>>,
+}
+
+impl<'de> MapAccess<'de> for ProbeMap<'_> {
+    type Error = ProbeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, ProbeError> {
+        match self.entries.next() {
+            Some(entry) => {
+                self.value = Some(*entry);
+                seed.deserialize(StrDeserializer::new(entry.0)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, ProbeError> {
+        let (name, dummy) = self.value.take().ok_or(ProbeError::Other)?;
+        seed.deserialize(ProbeValue {
+            name,
+            dummy,
+            read: self.read,
+        })
+    }
+}
+
+/// The deserializer of a value in the map.
+/// A value the struct reads with a type is recorded and answered with a default of that type,
+/// a value the struct collects for its flattened fields is answered with the dummy value.
+struct ProbeValue<'a> {
+    name: &'static str,
+    dummy: Dummy,
+    read: &'a RefCell<Vec<&'static str>>,
+}
+
+impl ProbeValue<'_> {
+    /// Records that the struct read the value with a type.
+    fn read(&self) {
+        self.read.borrow_mut().push(self.name);
+    }
+}
+
+/// Forwards typed reads to a visitor method with a default value.
+macro_rules! read_as {
+    ($($method:ident => $visit:ident($($value:expr)?)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+                self.read();
+                visitor.$visit($($value)?)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for ProbeValue<'_> {
+    type Error = ProbeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        match self.dummy {
+            Dummy::Str(text) | Dummy::Variant(text) => visitor.visit_str(text),
+            Dummy::Float => visitor.visit_f64(0.0),
+            Dummy::Int => visitor.visit_i64(0),
+            Dummy::Bool => visitor.visit_bool(false),
+            Dummy::Unit => visitor.visit_unit(),
+            Dummy::Seq => visitor.visit_seq(Empty),
+        }
+    }
+
+    read_as! {
+        deserialize_bool => visit_bool(false),
+        deserialize_i8 => visit_i64(0),
+        deserialize_i16 => visit_i64(0),
+        deserialize_i32 => visit_i64(0),
+        deserialize_i64 => visit_i64(0),
+        deserialize_u8 => visit_u64(0),
+        deserialize_u16 => visit_u64(0),
+        deserialize_u32 => visit_u64(0),
+        deserialize_u64 => visit_u64(0),
+        deserialize_f32 => visit_f64(0.0),
+        deserialize_f64 => visit_f64(0.0),
+        deserialize_char => visit_char(' '),
+        deserialize_str => visit_str(""),
+        deserialize_string => visit_str(""),
+        deserialize_bytes => visit_bytes(&[]),
+        deserialize_byte_buf => visit_bytes(&[]),
+        deserialize_option => visit_none(),
+        deserialize_unit => visit_unit(),
+        deserialize_seq => visit_seq(Empty),
+        deserialize_map => visit_map(Empty),
+        deserialize_identifier => visit_str(""),
+        deserialize_ignored_any => visit_unit(),
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, ProbeError> {
+        self.read();
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.read();
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, ProbeError> {
+        self.read();
+        visitor.visit_seq(Empty)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.read();
+        visitor.visit_seq(Empty)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.read();
+        visitor.visit_map(Empty)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.read();
+        Err(ProbeError::Other)
+    }
+}
+
+/// An empty sequence or map.
+struct Empty;
+
+impl<'de> SeqAccess<'de> for Empty {
+    type Error = ProbeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, _seed: T) -> Result<Option<T::Value>, ProbeError> {
+        Ok(None)
+    }
+}
+
+impl<'de> MapAccess<'de> for Empty {
+    type Error = ProbeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, _seed: K) -> Result<Option<K::Value>, ProbeError> {
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, _seed: V) -> Result<V::Value, ProbeError> {
+        Err(ProbeError::Other)
+    }
+}
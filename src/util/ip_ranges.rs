@@ -0,0 +1,121 @@
+//! A module that recognizes IP addresses the API can not locate, so they do not have to be sent.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::constant;
+use crate::error::IpApiError;
+use crate::model::ip_response::ErrorResponse;
+
+/// The kinds of addresses the API can not locate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpRange {
+    /// A private network, like RFC 1918 or an IPv6 unique local address.
+    Private,
+    /// Any other special purpose range, like loopback, link-local, CGNAT or documentation ranges.
+    Reserved,
+}
+
+/// The private IPv4 ranges as network address and prefix length.
+const PRIVATE_V4: [([u8; 4], u8); 3] = [([10, 0, 0, 0], 8), ([172, 16, 0, 0], 12), ([192, 168, 0, 0], 16)];
+
+/// The reserved IPv4 ranges as network address and prefix length.
+const RESERVED_V4: [([u8; 4], u8); 14] = [
+    ([0, 0, 0, 0], 8),
+    ([100, 64, 0, 0], 10),
+    ([127, 0, 0, 0], 8),
+    ([169, 254, 0, 0], 16),
+    ([192, 0, 0, 0], 24),
+    ([192, 0, 2, 0], 24),
+    ([192, 31, 196, 0], 24),
+    ([192, 52, 193, 0], 24),
+    ([192, 88, 99, 0], 24),
+    ([192, 175, 48, 0], 24),
+    ([198, 18, 0, 0], 15),
+    ([198, 51, 100, 0], 24),
+    ([203, 0, 113, 0], 24),
+    ([224, 0, 0, 0], 3),
+];
+
+/// The private IPv6 ranges as network address and prefix length.
+const PRIVATE_V6: [(u128, u8); 1] = [(0xfc00 << 112, 7)];
+
+/// The reserved IPv6 ranges as network address and prefix length.
+const RESERVED_V6: [(u128, u8); 7] = [
+    (0, 127),
+    (0x0064_ff9b_0001 << 80, 48),
+    (0x0100 << 112, 64),
+    (0x2001_0db8 << 96, 32),
+    (0x3fff << 112, 20),
+    (0xfe80 << 112, 10),
+    (0xff00 << 112, 8),
+];
+
+/// Classifies an IP address.
+///
+/// # Arguments
+/// * `ip` - The IP address.
+///
+/// # Returns
+/// * `Option<IpRange>` - The range of the address or `None` if the API can locate it.
+pub fn classify(ip: &IpAddr) -> Option<IpRange> {
+    match ip {
+        IpAddr::V4(ip) => classify_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => classify_v4(&ip),
+            None => classify_v6(ip),
+        },
+    }
+}
+
+/// Checks whether a query is an IP address the API can not locate.
+/// The error mirrors the one the API would answer with.
+///
+/// # Arguments
+/// * `query` - The query.
+///
+/// # Returns
+/// * `Option<IpApiError>` - `IpApiError::PrivateRange` or `IpApiError::ReservedRange`,
+///   or `None` if the query has to be sent.
+pub fn check_query(query: &str) -> Option<IpApiError> {
+    let range = classify(&query.parse::<IpAddr>().ok()?)?;
+    let message = match range {
+        IpRange::Private => constant::ERROR_PRIVATE_RANGE,
+        IpRange::Reserved => constant::ERROR_RESERVED_RANGE,
+    };
+    let response = ErrorResponse {
+        status: "fail".to_string(),
+        message: message.to_string(),
+        query: query.to_string(),
+    };
+    match range {
+        IpRange::Private => Some(IpApiError::PrivateRange(response)),
+        IpRange::Reserved => Some(IpApiError::ReservedRange(response)),
+    }
+}
+
+/// Classifies an IPv4 address.
+fn classify_v4(ip: &Ipv4Addr) -> Option<IpRange> {
+    let bits = u32::from(*ip);
+    let contains =
+        |(network, prefix): &([u8; 4], u8)| bits >> (32 - prefix) == u32::from_be_bytes(*network) >> (32 - prefix);
+    if PRIVATE_V4.iter().any(contains) {
+        return Some(IpRange::Private);
+    }
+    if RESERVED_V4.iter().any(contains) {
+        return Some(IpRange::Reserved);
+    }
+    None
+}
+
+/// Classifies an IPv6 address.
+fn classify_v6(ip: &Ipv6Addr) -> Option<IpRange> {
+    let bits = u128::from(*ip);
+    let contains = |(network, prefix): &(u128, u8)| bits >> (128 - prefix) == network >> (128 - prefix);
+    if PRIVATE_V6.iter().any(contains) {
+        return Some(IpRange::Private);
+    }
+    if RESERVED_V6.iter().any(contains) {
+        return Some(IpRange::Reserved);
+    }
+    None
+}
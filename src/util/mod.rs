@@ -1,4 +1,10 @@
 //! Contains utility functions for the wrapper.
 
+pub mod deserialize;
+pub mod introspect;
+pub mod ip_ranges;
+pub mod random;
+pub mod rate_limit;
 pub mod requests;
+pub mod retry;
 pub mod urls;
@@ -0,0 +1,36 @@
+//! A module that contains a small source of randomness without pulling in an extra dependency.
+//!
+//! The numbers are not cryptographically secure.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A counter mixed into every number, so two calls never hash the same input.
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Generates a random number.
+///
+/// # Returns
+/// * `u64` - The random number.
+pub fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.finish()
+}
+
+/// Generates a random number between 0 and 1.
+///
+/// # Returns
+/// * `f64` - The random number, 1 excluded.
+pub fn random_fraction() -> f64 {
+    (random_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Generates a random DNS label of 32 lowercase hex digits.
+///
+/// # Returns
+/// * `String` - The random label.
+pub fn random_label() -> String {
+    format!("{:016x}{:016x}", random_u64(), random_u64())
+}
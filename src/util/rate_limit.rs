@@ -0,0 +1,108 @@
+//! A module that keeps the client in sync with the rate limit reported by the API.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+
+use crate::constant;
+
+/// Defines how a client handles a request the API rejected because the rate limit is exceeded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitHandling {
+    /// Waits until the window resets and sends the request again.
+    /// After `constant::MAX_RATE_LIMIT_WAITS` waits, `IpApiError::RateLimited` is returned,
+    /// so a server that keeps rejecting the requests does not hang the caller.
+    #[default]
+    Wait,
+    /// Returns `IpApiError::RateLimited` right away.
+    ReturnError,
+}
+
+/// Tracks the rate limit the API reports through the `X-Rl` and `X-Ttl` headers.
+///
+/// The local quota only knows about the requests of this client.
+/// The API counts every request of the egress IP, so other processes can use up the window as well.
+/// Once the API reports that no requests are left, further requests wait until the window resets.
+#[derive(Debug, Default)]
+pub struct ServerRateLimit {
+    state: Mutex<ServerRateLimitState>,
+}
+
+/// The last rate limit state reported by the API.
+#[derive(Debug, Default, Clone, Copy)]
+struct ServerRateLimitState {
+    /// The amount of requests left in the current window.
+    remaining: Option<u32>,
+    /// The point in time at which the current window resets.
+    reset_at: Option<Instant>,
+}
+
+impl ServerRateLimit {
+    /// Creates a new ServerRateLimit that does not know about any window yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the state from the headers of a response.
+    /// Responses without the rate limit headers leave the state untouched.
+    ///
+    /// # Arguments
+    /// * `headers` - The headers of the response.
+    pub fn update(&self, headers: &HeaderMap) {
+        let remaining = parse_header(headers, constant::HEADER_REQUESTS_LEFT);
+        let ttl = parse_header(headers, constant::HEADER_TIME_TO_RESET);
+        if remaining.is_none() && ttl.is_none() {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.remaining = remaining;
+        state.reset_at = ttl.map(|ttl| Instant::now() + Duration::from_secs(ttl as u64));
+    }
+
+    /// Blocks further requests for the given duration.
+    /// Used when the API rejected a request without reporting its window.
+    ///
+    /// # Arguments
+    /// * `duration` - The time until the window resets.
+    pub fn block_for(&self, duration: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.remaining = Some(0);
+        state.reset_at = Some(Instant::now() + duration);
+    }
+
+    /// Gets the amount of requests the API reported as left in the current window.
+    ///
+    /// # Returns
+    /// * `Option<u32>` - The requests left or `None` if the API did not report them yet.
+    pub fn remaining(&self) -> Option<u32> {
+        self.state.lock().unwrap().remaining
+    }
+
+    /// Gets the time to wait before the next request may be sent.
+    ///
+    /// # Returns
+    /// * `Option<Duration>` - The time until the window resets or `None` if requests are left.
+    pub fn time_until_ready(&self) -> Option<Duration> {
+        let state = *self.state.lock().unwrap();
+        match (state.remaining, state.reset_at) {
+            (Some(0), Some(reset_at)) => {
+                let now = Instant::now();
+                (reset_at > now).then(|| reset_at - now)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses a numeric header.
+///
+/// # Arguments
+/// * `headers` - The headers to search.
+/// * `name` - The name of the header.
+///
+/// # Returns
+/// * `Option<u32>` - The value of the header or `None` if it is missing or not a number.
+fn parse_header(headers: &HeaderMap, name: &str) -> Option<u32> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
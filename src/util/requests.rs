@@ -9,8 +9,15 @@ use reqwest::header::HeaderMap;
 use reqwest::{Client, RequestBuilder};
 use serde::de::DeserializeOwned;
 
+use crate::constant;
+use crate::format::ResponseFormat;
+use crate::language::Language;
 use crate::model::ip_response::IpDefaultResponse;
-use crate::util::urls::build_url_from_struct;
+use crate::util::random::random_label;
+use crate::util::urls::{
+    append_language, build_batch_host, build_batch_url_on_host, build_edns_host, build_edns_url,
+    build_fields_param_from_struct, build_format_host, build_url_on_host, percent_encode,
+};
 
 /// Builds the request for a default response.
 ///
@@ -20,7 +27,7 @@ use crate::util::urls::build_url_from_struct;
 ///
 /// # Returns
 /// A `RequestBuilder` for the request.
-pub fn get_default_async_get_request(query: &String, client: &impl AsyncIpApi) -> RequestBuilder {
+pub fn get_default_async_get_request(query: &str, client: &impl AsyncIpApi) -> RequestBuilder {
     get_async_request::<IpDefaultResponse>(query, client)
 }
 
@@ -33,7 +40,7 @@ pub fn get_default_async_get_request(query: &String, client: &impl AsyncIpApi) -
 /// # Returns
 /// A `blocking::RequestBuilder` for the request.
 #[cfg(feature = "blocking")]
-pub fn get_default_blocking_get_request(query: &String, client: &impl BlockingIpApi) -> blocking::RequestBuilder {
+pub fn get_default_blocking_get_request(query: &str, client: &impl BlockingIpApi) -> blocking::RequestBuilder {
     get_blocking_get_request::<IpDefaultResponse>(query, client)
 }
 
@@ -46,15 +53,77 @@ pub fn get_default_blocking_get_request(query: &String, client: &impl BlockingIp
 ///
 /// # Returns
 /// A `RequestBuilder` for the request.
-pub fn get_async_request<T>(query: &String, client: &impl AsyncIpApi) -> RequestBuilder
+pub fn get_async_request<T>(query: &str, client: &impl AsyncIpApi) -> RequestBuilder
 where
     T: DeserializeOwned,
 {
-    let url = get_url::<T>(query, client);
-    match client.get_api_key() {
-        Some(_) => build_https_get_request(&url, client.get_api_key().as_ref().unwrap(), client.get_http_client()),
-        None => build_http_get_request(&url, client.get_http_client()),
-    }
+    get_async_localized_request::<T>(query, *client.get_language(), client)
+}
+
+/// Builds the async request for a custom response in the given language.
+/// The given struct must implement `DeserializeOwned`.
+///
+/// # Arguments
+/// * `query` - The IP address to query for.
+/// * `language` - The language of the response, `None` uses the default of the API.
+/// * `client` - The `AsyncIpApi` to use for the request.
+///
+/// # Returns
+/// A `RequestBuilder` for the request.
+pub fn get_async_localized_request<T>(
+    query: &str,
+    language: Option<Language>,
+    client: &impl AsyncIpApi,
+) -> RequestBuilder
+where
+    T: DeserializeOwned,
+{
+    get_async_format_request::<T>(query, language, client.get_response_format(), client)
+}
+
+/// Builds the async request for a custom response in the given language and format.
+/// The given struct must implement `DeserializeOwned`.
+///
+/// # Arguments
+/// * `query` - The IP address to query for.
+/// * `language` - The language of the response, `None` uses the default of the API.
+/// * `format` - The format of the response.
+/// * `client` - The `AsyncIpApi` to use for the request.
+///
+/// # Returns
+/// A `RequestBuilder` for the request.
+pub fn get_async_format_request<T>(
+    query: &str,
+    language: Option<Language>,
+    format: ResponseFormat,
+    client: &impl AsyncIpApi,
+) -> RequestBuilder
+where
+    T: DeserializeOwned,
+{
+    get_async_fields_request(query, &build_fields_param_from_struct::<T>(), language, format, client)
+}
+
+/// Builds the async request for the given fields in the given language and format.
+///
+/// # Arguments
+/// * `query` - The IP address to query for.
+/// * `fields` - The value of the `fields` parameter.
+/// * `language` - The language of the response, `None` uses the default of the API.
+/// * `format` - The format of the response.
+/// * `client` - The `AsyncIpApi` to use for the request.
+///
+/// # Returns
+/// A `RequestBuilder` for the request.
+pub fn get_async_fields_request(
+    query: &str,
+    fields: &str,
+    language: Option<Language>,
+    format: ResponseFormat,
+    client: &impl AsyncIpApi,
+) -> RequestBuilder {
+    let url = get_url(query, fields, language, format, client);
+    build_http_get_request(&url, format, client.get_http_client())
 }
 
 /// Builds the blocking request for a custom response.
@@ -67,114 +136,263 @@ where
 /// # Returns
 /// A `blocking::RequestBuilder` for the request.
 #[cfg(feature = "blocking")]
-pub fn get_blocking_get_request<T>(query: &String, client: &impl BlockingIpApi) -> blocking::RequestBuilder
+pub fn get_blocking_get_request<T>(query: &str, client: &impl BlockingIpApi) -> blocking::RequestBuilder
 where
     T: DeserializeOwned,
 {
-    let url = get_url::<T>(query, client);
-    match client.get_api_key() {
-        Some(_) => {
-            build_blocking_https_get_request(&url, client.get_api_key().as_ref().unwrap(), client.get_http_client())
-        }
-        None => build_blocking_http_get_request(&url, client.get_http_client()),
-    }
+    get_blocking_localized_get_request::<T>(query, *client.get_language(), client)
 }
 
-/// Builds a https request.
+/// Builds the blocking request for a custom response in the given language.
+/// The given struct must implement `DeserializeOwned`.
 ///
 /// # Arguments
-/// * `url` - The url to build the request for.
-/// * `api_key` - The API key to use for the request.
-/// * `client` - The `Client` to use for the request.
+/// * `query` - The IP address to query for.
+/// * `language` - The language of the response, `None` uses the default of the API.
+/// * `client` - The `BlockingIpApi` to use for the request.
+///
+/// # Returns
+/// A `blocking::RequestBuilder` for the request.
+#[cfg(feature = "blocking")]
+pub fn get_blocking_localized_get_request<T>(
+    query: &str,
+    language: Option<Language>,
+    client: &impl BlockingIpApi,
+) -> blocking::RequestBuilder
+where
+    T: DeserializeOwned,
+{
+    get_blocking_format_request::<T>(query, language, client.get_response_format(), client)
+}
+
+/// Builds the blocking request for a custom response in the given language and format.
+/// The given struct must implement `DeserializeOwned`.
+///
+/// # Arguments
+/// * `query` - The IP address to query for.
+/// * `language` - The language of the response, `None` uses the default of the API.
+/// * `format` - The format of the response.
+/// * `client` - The `BlockingIpApi` to use for the request.
+///
+/// # Returns
+/// A `blocking::RequestBuilder` for the request.
+#[cfg(feature = "blocking")]
+pub fn get_blocking_format_request<T>(
+    query: &str,
+    language: Option<Language>,
+    format: ResponseFormat,
+    client: &impl BlockingIpApi,
+) -> blocking::RequestBuilder
+where
+    T: DeserializeOwned,
+{
+    get_blocking_fields_request(query, &build_fields_param_from_struct::<T>(), language, format, client)
+}
+
+/// Builds the blocking request for the given fields in the given language and format.
+///
+/// # Arguments
+/// * `query` - The IP address to query for.
+/// * `fields` - The value of the `fields` parameter.
+/// * `language` - The language of the response, `None` uses the default of the API.
+/// * `format` - The format of the response.
+/// * `client` - The `BlockingIpApi` to use for the request.
+///
+/// # Returns
+/// A `blocking::RequestBuilder` for the request.
+#[cfg(feature = "blocking")]
+pub fn get_blocking_fields_request(
+    query: &str,
+    fields: &str,
+    language: Option<Language>,
+    format: ResponseFormat,
+    client: &impl BlockingIpApi,
+) -> blocking::RequestBuilder {
+    let url = get_url(query, fields, language, format, client);
+    build_blocking_http_get_request(&url, format, client.get_http_client())
+}
+
+/// Builds the async batch request for a custom response.
+/// The given struct must implement `DeserializeOwned`.
+///
+/// # Arguments
+/// * `queries` - The IP addresses to query for.
+/// * `client` - The `AsyncIpApi` to use for the request.
 ///
 /// # Returns
 /// A `RequestBuilder` for the request.
-fn build_https_get_request(url: &String, api_key: &str, client: &Client) -> RequestBuilder {
-    client.get(url).headers(build_https_header(api_key))
+pub fn get_async_batch_request<T>(queries: &[&str], client: &impl AsyncIpApi) -> RequestBuilder
+where
+    T: DeserializeOwned,
+{
+    let url = get_batch_url::<T>(client);
+    client.get_http_client().post(url).json(queries).headers(build_http_header(ResponseFormat::Json))
 }
 
-/// Builds a blocking https request.
+/// Builds the blocking batch request for a custom response.
+/// The given struct must implement `DeserializeOwned`.
 ///
 /// # Arguments
-/// * `url` - The url to build the request for.
-/// * `api_key` - The API key to use for the request.
-/// * `client` - The `blocking::Client` to use for the request.
+/// * `queries` - The IP addresses to query for.
+/// * `client` - The `BlockingIpApi` to use for the request.
+///
+/// # Returns
+/// A `blocking::RequestBuilder` for the request.
+#[cfg(feature = "blocking")]
+pub fn get_blocking_batch_request<T>(queries: &[&str], client: &impl BlockingIpApi) -> blocking::RequestBuilder
+where
+    T: DeserializeOwned,
+{
+    let url = get_batch_url::<T>(client);
+    client.get_http_client().post(url).json(queries).headers(build_http_header(ResponseFormat::Json))
+}
+
+/// Builds the async request for the EDNS endpoint.
+///
+/// # Arguments
+/// * `client` - The `AsyncIpApi` to use for the request.
 ///
 /// # Returns
 /// A `RequestBuilder` for the request.
+pub fn get_async_edns_request(client: &impl AsyncIpApi) -> RequestBuilder {
+    build_http_get_request(&get_edns_url(client), ResponseFormat::Json, client.get_http_client())
+}
+
+/// Builds the blocking request for the EDNS endpoint.
+///
+/// # Arguments
+/// * `client` - The `BlockingIpApi` to use for the request.
+///
+/// # Returns
+/// A `blocking::RequestBuilder` for the request.
 #[cfg(feature = "blocking")]
-fn build_blocking_https_get_request(
-    url: &String,
-    api_key: &str,
-    client: &blocking::Client,
-) -> blocking::RequestBuilder {
-    client.get(url).headers(build_https_header(api_key))
+pub fn get_blocking_edns_request(client: &impl BlockingIpApi) -> blocking::RequestBuilder {
+    build_blocking_http_get_request(&get_edns_url(client), ResponseFormat::Json, client.get_http_client())
 }
 
 /// Builds a http request.
 ///
 /// # Arguments
 /// * `url` - The url to build the request for.
+/// * `format` - The format of the response.
 /// * `client` - The `Client` to use for the request.
 ///
 /// # Returns
 /// A `RequestBuilder` for the request.
-fn build_http_get_request(url: &String, client: &Client) -> RequestBuilder {
-    client.get(url).headers(build_http_header())
+fn build_http_get_request(url: &String, format: ResponseFormat, client: &Client) -> RequestBuilder {
+    client.get(url).headers(build_http_header(format))
 }
 
 /// Builds a blocking http request.
 ///
 /// # Arguments
 /// * `url` - The url to build the request for.
+/// * `format` - The format of the response.
 /// * `client` - The `blocking::Client` to use for the request.
 ///
 /// # Returns
 /// A `RequestBuilder` for the request.
 #[cfg(feature = "blocking")]
-fn build_blocking_http_get_request(url: &String, client: &blocking::Client) -> blocking::RequestBuilder {
-    client.get(url).headers(build_http_header())
+fn build_blocking_http_get_request(
+    url: &String,
+    format: ResponseFormat,
+    client: &blocking::Client,
+) -> blocking::RequestBuilder {
+    client.get(url).headers(build_http_header(format))
 }
 
 /// Builds the url for a request.
+/// The query is percent-encoded.
+/// Clients with a custom base url use it, clients with an API key use the pro tier and all others the free tier.
 ///
 /// # Arguments
 /// * `query` - The IP address to query for.
+/// * `fields` - The value of the `fields` parameter.
+/// * `language` - The language of the response, `None` uses the default of the API.
+/// * `format` - The format of the response.
+/// * `client` - The `IpApiClient` to use for the request.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+fn get_url(
+    query: &str,
+    fields: &str,
+    language: Option<Language>,
+    format: ResponseFormat,
+    client: &impl IpApi,
+) -> String {
+    let base_url = match (client.get_base_url(), client.get_api_key()) {
+        (Some(base_url), _) => base_url.as_str(),
+        (None, Some(_)) => constant::PRO_BASE_URL,
+        (None, None) => constant::HTTP_BASE_URL,
+    };
+    let host = build_format_host(base_url, format);
+    let url = build_url_on_host(&host, &percent_encode(query), fields, client.get_api_key().as_deref());
+    localize_url(url, language)
+}
+
+/// Builds the url for a batch request.
+/// Clients with a custom base url use it, clients with an API key use the pro tier and all others the free tier.
+///
+/// # Arguments
 /// * `client` - The `IpApiClient` to use for the request.
 ///
 /// # Returns
 /// A `String` containing the url for the request.
-fn get_url<T>(query: &String, client: &impl IpApi) -> String
+fn get_batch_url<T>(client: &impl IpApi) -> String
 where
     T: DeserializeOwned,
 {
-    match client.get_api_key() {
-        Some(_) => build_url_from_struct::<T>(true, query),
-        None => build_url_from_struct::<T>(false, query),
+    let host = match (client.get_base_url(), client.get_api_key()) {
+        (Some(base_url), _) => build_batch_host(base_url),
+        (None, Some(_)) => constant::PRO_BATCH_HOST.to_string(),
+        (None, None) => constant::HTTP_BATCH_HOST.to_string(),
+    };
+    let fields = build_fields_param_from_struct::<T>();
+    let url = build_batch_url_on_host(&host, &fields, client.get_api_key().as_deref());
+    localize_url(url, *client.get_language())
+}
+
+/// Builds the url for a request to the EDNS endpoint.
+/// Clients with a custom EDNS base url use it, all others a random subdomain of the EDNS endpoint.
+///
+/// # Arguments
+/// * `client` - The `IpApiClient` to use for the request.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+fn get_edns_url(client: &impl IpApi) -> String {
+    match client.get_edns_base_url() {
+        Some(base_url) => build_edns_host(base_url),
+        None => build_edns_url(&random_label()),
     }
 }
 
-/// Builds an header that set the `Accept` and `User-Agent` fields.
+/// Adds the `lang` parameter to the url, if a language is set.
+///
+/// # Arguments
+/// * `url` - The url to localize.
+/// * `language` - The language of the response, `None` uses the default of the API.
 ///
 /// # Returns
-/// A `HeaderMap` containing the headers.
-fn build_http_header() -> HeaderMap {
-    let mut headers = HeaderMap::new();
-    headers.insert("Accept", "application/json".parse().unwrap());
-    //headers.insert("User-Agent", format!("ip-api-rust/{}", env!("CARGO_PKG_VERSION")).parse().unwrap());
-    headers
+/// A `String` containing the localized url.
+fn localize_url(url: String, language: Option<Language>) -> String {
+    match language {
+        Some(language) => append_language(url, language),
+        None => url,
+    }
 }
 
-/// Builds an header that extends the header from `build_http_header`.
-/// Adds the api_key to the header.
+/// Builds an header that set the `Accept` and `User-Agent` fields.
 ///
 /// # Arguments
-/// * `api_key` - The API key to use for the request.
+/// * `format` - The format of the response, which decides the `Accept` field.
 ///
 /// # Returns
 /// A `HeaderMap` containing the headers.
-fn build_https_header(api_key: &str) -> HeaderMap {
-    let mut headers = build_http_header();
-    headers.insert("Authorization: Bearer", api_key.parse().unwrap());
+fn build_http_header(format: ResponseFormat) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("Accept", format.media_type().parse().unwrap());
+    //headers.insert("User-Agent", format!("ip-api-rust/{}", env!("CARGO_PKG_VERSION")).parse().unwrap());
     headers
 }
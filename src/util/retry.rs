@@ -0,0 +1,112 @@
+//! A module that contains the policy for retrying requests that failed for transient reasons.
+
+use std::time::Duration;
+
+use crate::error::IpApiError;
+use crate::util::random::random_fraction;
+
+/// Defines whether and how often a failed request is sent again.
+///
+/// The delay before a retry grows exponentially with every attempt, starting at the `base_delay`.
+/// Errors of the API itself, like `IpApiError::ReservedRange` or `IpApiError::InvalidQuery`, are never retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum amount of attempts, including the first one.
+    pub max_attempts: u32,
+    /// The delay before the first retry.
+    pub base_delay: Duration,
+    /// The upper bound of the delay between two attempts.
+    pub max_delay: Duration,
+    /// Whether the delay is randomized, so that many clients do not retry at the same time.
+    pub jitter: bool,
+    /// Whether errors while connecting to the API are retried.
+    pub retry_connect: bool,
+    /// Whether timed out requests are retried.
+    pub retry_timeout: bool,
+    /// Whether responses with a 5xx status code are retried.
+    pub retry_server_error: bool,
+    /// Whether requests rejected with HTTP 429 are retried once the window of the API resets.
+    /// With `RateLimitHandling::Wait` it only applies once the request waited `constant::MAX_RATE_LIMIT_WAITS` times.
+    pub retry_rate_limited: bool,
+}
+
+impl RetryPolicy {
+    /// Creates a new RetryPolicy that retries every transient error with jitter.
+    ///
+    /// # Arguments
+    /// * `max_attempts` - The maximum amount of attempts, including the first one.
+    /// * `base_delay` - The delay before the first retry.
+    pub fn new(max_attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            base_delay,
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retry_connect: true,
+            retry_timeout: true,
+            retry_server_error: true,
+            retry_rate_limited: true,
+        }
+    }
+
+    /// Creates a new RetryPolicy that never retries.
+    pub fn none() -> Self {
+        Self::new(1, Duration::ZERO)
+    }
+
+    /// Gets the delay before the next attempt.
+    ///
+    /// # Arguments
+    /// * `error` - The error of the failed attempt.
+    /// * `attempt` - The number of the failed attempt, starting at 1.
+    ///
+    /// # Returns
+    /// * `Option<Duration>` - The delay or `None` if the request must not be retried.
+    pub fn delay_for(&self, error: &IpApiError, attempt: u32) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.is_retryable(error) {
+            return None;
+        }
+        if let IpApiError::RateLimited {
+            retry_after,
+        } = error
+        {
+            return Some(*retry_after);
+        }
+        let delay = self.base_delay.saturating_mul(2u32.saturating_pow(attempt - 1)).min(self.max_delay);
+        match self.jitter {
+            true => Some(delay.mul_f64(0.5 + random_fraction() / 2.0)),
+            false => Some(delay),
+        }
+    }
+
+    /// Checks whether an error belongs to a class this policy retries.
+    ///
+    /// # Arguments
+    /// * `error` - The error of the failed attempt.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the error is retryable.
+    pub fn is_retryable(&self, error: &IpApiError) -> bool {
+        match error {
+            IpApiError::RateLimited {
+                ..
+            } => self.retry_rate_limited,
+            IpApiError::HttpStatus {
+                status,
+                ..
+            } => self.retry_server_error && (500..600).contains(status),
+            IpApiError::ReqwestError(err) if err.is_timeout() => self.retry_timeout,
+            IpApiError::ReqwestError(err) if err.is_connect() => self.retry_connect,
+            IpApiError::ReqwestError(err) => {
+                self.retry_server_error && err.status().is_some_and(|status| status.is_server_error())
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
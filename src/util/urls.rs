@@ -1,10 +1,14 @@
 //! A module that helps you building the correct url for a request.
 
 use serde::Deserialize;
-use serde_aux::prelude::serde_introspect;
+
+use crate::field::{Field, FieldSet};
+use crate::format::ResponseFormat;
+use crate::language::Language;
+use crate::util::introspect::struct_fields;
 
 /// Builds the url for a request.
-/// The fields are automatically extracted from the struct.
+/// The fields are automatically extracted from the struct and encoded like `build_fields_param_from_struct` does.
 ///
 /// # Arguments
 /// * `https` - A `bool` indicating whether the url should be built for a HTTPS request or not.
@@ -12,44 +16,41 @@ use serde_aux::prelude::serde_introspect;
 ///
 /// # Returns
 /// A `String` containing the url for the request.
-pub fn build_url_from_struct<'de, T>(https: bool, ip: &String) -> String
+pub fn build_url_from_struct<'de, T>(https: bool, ip: &str) -> String
 where
     T: Deserialize<'de>,
 {
-    let fields = serde_introspect::<T>();
-    build_url_with_fields(https, ip, fields)
+    build_url_on_host(default_host(https), &percent_encode(ip), &build_fields_param_from_struct::<T>(), None)
 }
 
 /// Builds the HTTP url for a request.
-/// The fields are automatically extracted from the struct.
+/// The fields are automatically extracted from the struct and encoded like `build_fields_param_from_struct` does.
 ///
 /// # Arguments
 /// * `ip` - The IP address to query for.
 ///
 /// # Returns
 /// A `String` containing the url for the request.
-pub fn build_http_url_from_struct<'de, T>(ip: &String) -> String
+pub fn build_http_url_from_struct<'de, T>(ip: &str) -> String
 where
     T: Deserialize<'de>,
 {
-    let fields = serde_introspect::<T>();
-    build_url_with_fields(false, ip, fields)
+    build_url_from_struct::<T>(false, ip)
 }
 
 /// Builds the HTTPS url for a request.
-/// The fields are automatically extracted from the struct.
+/// The fields are automatically extracted from the struct and encoded like `build_fields_param_from_struct` does.
 ///
 /// # Arguments
 /// * `ip` - The IP address to query for.
 ///
 /// # Returns
 /// A `String` containing the url for the request.
-pub fn build_https_url_from_struct<'de, T>(ip: &String) -> String
+pub fn build_https_url_from_struct<'de, T>(ip: &str) -> String
 where
     T: Deserialize<'de>,
 {
-    let fields = serde_introspect::<T>();
-    build_url_with_fields(true, ip, fields)
+    build_url_from_struct::<T>(true, ip)
 }
 
 /// Builds the url for a request without fields.
@@ -59,11 +60,8 @@ where
 ///
 /// # Returns
 /// A `String` containing the url for the request.
-pub fn build_url_without_fields(https: bool, ip: &String) -> String {
-    match https {
-        true => format!("{}{}", crate::constant::HTTPS_HOST, ip),
-        false => format!("{}{}", crate::constant::HTTP_HOST, ip),
-    }
+pub fn build_url_without_fields(https: bool, ip: &str) -> String {
+    format!("{}{}", default_host(https), percent_encode(ip))
 }
 
 /// Builds the url for a request with fields.
@@ -73,14 +71,426 @@ pub fn build_url_without_fields(https: bool, ip: &String) -> String {
 ///
 /// # Returns
 /// A `String` containing the url for the request.
-pub fn build_url_with_fields(https: bool, ip: &String, fields: &[&'static str]) -> String {
+pub fn build_url_with_fields(https: bool, ip: &str, fields: &[&'static str]) -> String {
+    build_url_on_host(default_host(https), &percent_encode(ip), &build_fields_param(fields), None)
+}
+
+/// Builds the url for a request with a set of fields.
+/// The fields are encoded as numeric bitmask, which keeps the url short.
+///
+/// # Arguments
+/// * `https` - A `bool` indicating whether the url should be built for a HTTPS request or not.
+/// * `ip` - The IP address to query for.
+/// * `fields` - The fields to request.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_url_with_field_set(https: bool, ip: &str, fields: FieldSet) -> String {
+    build_url_on_host(default_host(https), &percent_encode(ip), &build_field_set_param(fields), None)
+}
+
+/// Builds the pro tier url for a request.
+/// The fields are automatically extracted from the struct and encoded like `build_fields_param_from_struct` does.
+///
+/// # Arguments
+/// * `ip` - The IP address to query for.
+/// * `api_key` - The API key of the pro tier.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_pro_url_from_struct<'de, T>(ip: &str, api_key: &str) -> String
+where
+    T: Deserialize<'de>,
+{
+    build_url_on_host(
+        crate::constant::PRO_HOST,
+        &percent_encode(ip),
+        &build_fields_param_from_struct::<T>(),
+        Some(api_key),
+    )
+}
+
+/// Builds the pro tier url for a request without fields.
+///
+/// # Arguments
+/// * `ip` - The IP address to query for.
+/// * `api_key` - The API key of the pro tier.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_pro_url_without_fields(ip: &str, api_key: &str) -> String {
+    format!("{}{}?key={}", crate::constant::PRO_HOST, percent_encode(ip), percent_encode(api_key))
+}
+
+/// Builds the pro tier url for a request with fields.
+///
+/// # Arguments
+/// * `ip` - The IP address to query for.
+/// * `fields` - The fields to request.
+/// * `api_key` - The API key of the pro tier.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_pro_url_with_fields(ip: &str, fields: &[&'static str], api_key: &str) -> String {
+    build_url_on_host(crate::constant::PRO_HOST, &percent_encode(ip), &build_fields_param(fields), Some(api_key))
+}
+
+/// Builds the pro tier url for a request with a set of fields.
+/// The fields are encoded as numeric bitmask, which keeps the url short.
+///
+/// # Arguments
+/// * `ip` - The IP address to query for.
+/// * `fields` - The fields to request.
+/// * `api_key` - The API key of the pro tier.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_pro_url_with_field_set(ip: &str, fields: FieldSet, api_key: &str) -> String {
+    build_url_on_host(crate::constant::PRO_HOST, &percent_encode(ip), &build_field_set_param(fields), Some(api_key))
+}
+
+/// Builds the url for a batch request.
+/// The fields are automatically extracted from the struct and encoded like `build_fields_param_from_struct` does.
+///
+/// # Arguments
+/// * `https` - A `bool` indicating whether the url should be built for a HTTPS request or not.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_batch_url_from_struct<'de, T>(https: bool) -> String
+where
+    T: Deserialize<'de>,
+{
+    build_batch_url_on_host(default_batch_host(https), &build_fields_param_from_struct::<T>(), None)
+}
+
+/// Builds the url for a batch request with fields.
+/// The fields apply to every query in the batch.
+///
+/// # Arguments
+/// * `https` - A `bool` indicating whether the url should be built for a HTTPS request or not.
+/// * `fields` - The fields to request.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_batch_url_with_fields(https: bool, fields: &[&'static str]) -> String {
+    build_batch_url_on_host(default_batch_host(https), &build_fields_param(fields), None)
+}
+
+/// Builds the url for a batch request with a set of fields.
+/// The fields apply to every query in the batch and are encoded as numeric bitmask.
+///
+/// # Arguments
+/// * `https` - A `bool` indicating whether the url should be built for a HTTPS request or not.
+/// * `fields` - The fields to request.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_batch_url_with_field_set(https: bool, fields: FieldSet) -> String {
+    build_batch_url_on_host(default_batch_host(https), &build_field_set_param(fields), None)
+}
+
+/// Builds the pro tier url for a batch request.
+/// The fields are automatically extracted from the struct and encoded like `build_fields_param_from_struct` does.
+///
+/// # Arguments
+/// * `api_key` - The API key of the pro tier.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_pro_batch_url_from_struct<'de, T>(api_key: &str) -> String
+where
+    T: Deserialize<'de>,
+{
+    build_batch_url_on_host(crate::constant::PRO_BATCH_HOST, &build_fields_param_from_struct::<T>(), Some(api_key))
+}
+
+/// Builds the pro tier url for a batch request with fields.
+/// The fields apply to every query in the batch.
+///
+/// # Arguments
+/// * `fields` - The fields to request.
+/// * `api_key` - The API key of the pro tier.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_pro_batch_url_with_fields(fields: &[&'static str], api_key: &str) -> String {
+    build_batch_url_on_host(crate::constant::PRO_BATCH_HOST, &build_fields_param(fields), Some(api_key))
+}
+
+/// Builds the pro tier url for a batch request with a set of fields.
+/// The fields apply to every query in the batch and are encoded as numeric bitmask.
+///
+/// # Arguments
+/// * `fields` - The fields to request.
+/// * `api_key` - The API key of the pro tier.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_pro_batch_url_with_field_set(fields: FieldSet, api_key: &str) -> String {
+    build_batch_url_on_host(crate::constant::PRO_BATCH_HOST, &build_field_set_param(fields), Some(api_key))
+}
+
+/// Builds the host for single requests on a custom base url.
+/// A custom base url points the client at a local stand-in server, a caching proxy or a mirror.
+///
+/// # Arguments
+/// * `base_url` - The base url, like `http://localhost:8080`.
+///
+/// # Returns
+/// A `String` containing the host for single requests.
+pub fn build_host(base_url: &str) -> String {
+    build_format_host(base_url, ResponseFormat::Json)
+}
+
+/// Builds the host for single requests in a response format.
+///
+/// # Arguments
+/// * `base_url` - The base url, like `http://ip-api.com`.
+/// * `format` - The format the API answers in.
+///
+/// # Returns
+/// A `String` containing the host for single requests, like `http://ip-api.com/csv/`.
+pub fn build_format_host(base_url: &str, format: ResponseFormat) -> String {
+    format!("{}/{}/", base_url.trim_end_matches('/'), format.path())
+}
+
+/// Builds the host for batch requests on a custom base url.
+///
+/// # Arguments
+/// * `base_url` - The base url, like `http://localhost:8080`.
+///
+/// # Returns
+/// A `String` containing the host for batch requests.
+pub fn build_batch_host(base_url: &str) -> String {
+    format!("{}/batch", base_url.trim_end_matches('/'))
+}
+
+/// Builds the url of the EDNS endpoint on a custom base url.
+///
+/// # Arguments
+/// * `base_url` - The base url, like `http://localhost:8080`.
+///
+/// # Returns
+/// A `String` containing the url of the EDNS endpoint.
+pub fn build_edns_host(base_url: &str) -> String {
+    format!("{}/json", base_url.trim_end_matches('/'))
+}
+
+/// Builds the url of the EDNS endpoint on a subdomain of the ip-api.com service.
+///
+/// # Arguments
+/// * `subdomain` - The subdomain, random so that no resolver has it cached.
+///
+/// # Returns
+/// A `String` containing the url of the EDNS endpoint, like `http://{subdomain}.edns.ip-api.com/json`.
+pub fn build_edns_url(subdomain: &str) -> String {
+    format!("http://{}.{}/json", subdomain, crate::constant::EDNS_DOMAIN)
+}
+
+/// Builds the url for a request on the given host.
+///
+/// # Arguments
+/// * `host` - The host to send the request to, like `constant::HTTP_HOST`.
+/// * `ip` - The IP address to query for.
+/// * `fields` - The value of the `fields` parameter.
+/// * `api_key` - The API key to add to the url, if any.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_url_on_host(host: &str, ip: &str, fields: &str, api_key: Option<&str>) -> String {
+    append_api_key(format!("{}{}?fields={}", host, ip, fields), api_key)
+}
+
+/// Builds the url for a batch request on the given host.
+///
+/// # Arguments
+/// * `host` - The host to send the request to, like `constant::HTTP_BATCH_HOST`.
+/// * `fields` - The value of the `fields` parameter.
+/// * `api_key` - The API key to add to the url, if any.
+///
+/// # Returns
+/// A `String` containing the url for the request.
+pub fn build_batch_url_on_host(host: &str, fields: &str, api_key: Option<&str>) -> String {
+    append_api_key(format!("{}?fields={}", host, fields), api_key)
+}
+
+/// Builds the value of the `fields` parameter for a struct.
+/// The fields are encoded as numeric bitmask, unless the struct contains names the API does not know.
+///
+/// # Returns
+/// A `String` containing the value of the `fields` parameter.
+pub fn build_fields_param_from_struct<'de, T>() -> String
+where
+    T: Deserialize<'de>,
+{
+    let fields = struct_fields::<T>();
+    match FieldSet::from_names(&fields) {
+        Some(field_set) => build_field_set_param(field_set),
+        None => build_fields_param(&fields),
+    }
+}
+
+/// Builds the value of the `fields` parameter for a set of fields.
+/// The `message` field is always requested, so failed responses contain it.
+///
+/// # Arguments
+/// * `fields` - The fields to request.
+///
+/// # Returns
+/// A `String` containing the value of the `fields` parameter.
+pub fn build_fields_param_from_field_set(fields: FieldSet) -> String {
+    build_field_set_param(fields)
+}
+
+/// Adds the `lang` parameter to a url, so the API localizes the names in the response.
+///
+/// # Arguments
+/// * `url` - The url to add the parameter to.
+/// * `language` - The language of the response.
+///
+/// # Returns
+/// A `String` containing the url with the `lang` parameter.
+pub fn append_language(url: String, language: Language) -> String {
+    let separator = if url.contains('?') {
+        '&'
+    } else {
+        '?'
+    };
+    format!("{}{}lang={}", url, separator, language)
+}
+
+/// Extracts the query from the url of a single request, like `8.8.8.8` from `http://ip-api.com/json/8.8.8.8`.
+/// The url may use any of the response formats.
+///
+/// # Arguments
+/// * `url` - The url of the request.
+///
+/// # Returns
+/// An `Option<String>` containing the decoded query or `None` if the url is not the one of a single request.
+pub fn query_from_url(url: &reqwest::Url) -> Option<String> {
+    let mut segments = url.path_segments()?.rev();
+    let query = segments.next()?;
+    match segments.next().and_then(ResponseFormat::from_path) {
+        Some(_) if !query.is_empty() => Some(percent_decode(query)),
+        _ => None,
+    }
+}
+
+/// Percent-encodes a string for use as a path segment.
+/// Unreserved characters and the `:` of IPv6 addresses are kept as they are.
+///
+/// # Arguments
+/// * `value` - The string to encode.
+///
+/// # Returns
+/// A `String` containing the encoded value.
+pub fn percent_encode(value: &str) -> String {
+    value.bytes().fold(String::with_capacity(value.len()), |mut encoded, byte| {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+        encoded
+    })
+}
+
+/// Decodes a percent-encoded string.
+///
+/// # Arguments
+/// * `value` - The percent-encoded string.
+///
+/// # Returns
+/// A `String` containing the decoded value, invalid UTF-8 is replaced.
+pub fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes.get(index + 1..index + 3).and_then(|hex| std::str::from_utf8(hex).ok());
+        match (bytes[index], hex.and_then(|hex| u8::from_str_radix(hex, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Adds the `key` parameter to a url, if an API key is given.
+/// The key is percent-encoded, so it cannot add or change other parameters of the url.
+///
+/// # Arguments
+/// * `url` - The url to add the parameter to.
+/// * `api_key` - The API key of the pro tier.
+///
+/// # Returns
+/// A `String` containing the url with the `key` parameter.
+fn append_api_key(url: String, api_key: Option<&str>) -> String {
+    match api_key {
+        Some(api_key) => format!("{}&key={}", url, percent_encode(api_key)),
+        None => url,
+    }
+}
+
+/// Gets the host of the free tier for single requests.
+///
+/// # Arguments
+/// * `https` - A `bool` indicating whether the host should be the HTTPS host or not.
+///
+/// # Returns
+/// The host for single requests.
+fn default_host(https: bool) -> &'static str {
+    match https {
+        true => crate::constant::HTTPS_HOST,
+        false => crate::constant::HTTP_HOST,
+    }
+}
+
+/// Gets the host of the free tier for batch requests.
+///
+/// # Arguments
+/// * `https` - A `bool` indicating whether the host should be the HTTPS host or not.
+///
+/// # Returns
+/// The host for batch requests.
+fn default_batch_host(https: bool) -> &'static str {
+    match https {
+        true => crate::constant::HTTPS_BATCH_HOST,
+        false => crate::constant::HTTP_BATCH_HOST,
+    }
+}
+
+/// Builds the value of the `fields` parameter.
+/// The `message` field is always added, so we get the error message if an error occurs.
+///
+/// # Arguments
+/// * `fields` - The fields to request.
+///
+/// # Returns
+/// A `String` containing the comma separated fields.
+fn build_fields_param(fields: &[&'static str]) -> String {
     let mut fields_str = fields.join(",");
     if !fields.contains(&"message") {
         //We do this to get the error message if an error occurs.
         fields_str = fields.join(",") + ",message";
     }
-    match https {
-        true => format!("{}{}?fields={}", crate::constant::HTTPS_HOST, ip, fields_str),
-        false => format!("{}{}?fields={}", crate::constant::HTTP_HOST, ip, fields_str),
-    }
+    fields_str
+}
+
+/// Builds the numeric value of the `fields` parameter.
+/// The `message` field is always added, so we get the error message if an error occurs.
+///
+/// # Arguments
+/// * `fields` - The fields to request.
+///
+/// # Returns
+/// A `String` containing the bitmask of the fields.
+fn build_field_set_param(fields: FieldSet) -> String {
+    fields.with(Field::Message).to_string()
 }
@@ -1,2 +1,3 @@
 pub const TEST_IP: &str = "127.0.0.1";
 pub const EXTERN_TEST_IP: &str = "8.8.8.8";
+pub const TEST_API_KEY: &str = "test-key";
@@ -48,3 +48,29 @@ async fn test_error_invalid_query() {
         _ => panic!("Wrong error type returned."),
     }
 }
+
+#[tokio::test]
+async fn test_client_batch() {
+    let client = IpApiClient::new();
+    let results = client.query_api_batch::<IpDefaultResponse>(&[EXTERN_TEST_IP, "127.0.0.1"]).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap(), &get_expected_default_response());
+    assert!(matches!(results[1], Err(IpApiError::ReservedRange(_))));
+}
+
+#[tokio::test]
+async fn test_error_batch_too_large() {
+    let client = IpApiClient::new();
+    let queries = vec![EXTERN_TEST_IP; 101];
+    let result = client.query_api_batch::<IpDefaultResponse>(&queries).await;
+    assert!(matches!(result, Err(IpApiError::BatchTooLarge(101))));
+}
+
+#[tokio::test]
+async fn test_client_bulk() {
+    let client = IpApiClient::new();
+    let queries = vec![EXTERN_TEST_IP; 150];
+    let results = client.query_api_bulk::<IpDefaultResponse, _>(queries).await.unwrap();
+    assert_eq!(results.len(), 150);
+    assert!(results.iter().all(|result| result.as_ref().unwrap() == &get_expected_default_response()));
+}
@@ -48,3 +48,29 @@ fn test_error_invalid_query() {
         _ => panic!("Wrong error type returned."),
     }
 }
+
+#[test]
+fn test_blocking_client_batch() {
+    let client = BlockingIpApiClient::new();
+    let results = client.query_api_batch::<IpDefaultResponse>(&[EXTERN_TEST_IP, "127.0.0.1"]).unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap(), &get_expected_default_response());
+    assert!(matches!(results[1], Err(IpApiError::ReservedRange(_))));
+}
+
+#[test]
+fn test_blocking_client_bulk() {
+    let client = BlockingIpApiClient::new();
+    let queries = vec![EXTERN_TEST_IP; 150];
+    let results = client.query_api_bulk::<IpDefaultResponse, _>(queries).unwrap();
+    assert_eq!(results.len(), 150);
+    assert!(results.iter().all(|result| result.as_ref().unwrap() == &get_expected_default_response()));
+}
+
+#[test]
+fn test_blocking_error_batch_too_large() {
+    let client = BlockingIpApiClient::new();
+    let queries = vec![EXTERN_TEST_IP; 101];
+    let result = client.query_api_batch::<IpDefaultResponse>(&queries);
+    assert!(matches!(result, Err(IpApiError::BatchTooLarge(101))));
+}
@@ -0,0 +1,96 @@
+use crate::constants::{EXTERN_TEST_IP, TEST_IP};
+use crate::end2end::client::utils::get_expected_default_response;
+use crate::offline::utils::build_fixtures;
+use ip_api4rs::client::AsyncIpApi;
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::model::ip_response::IpDefaultResponse;
+use ip_api4rs::test_util::server::MockServer;
+use ip_api4rs::IpApiClient;
+
+fn build_client(server: &MockServer) -> IpApiClient {
+    IpApiClient::builder().base_url(server.base_url()).build().unwrap()
+}
+
+#[tokio::test]
+async fn test_client() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let result = build_client(&server).query_api_default(EXTERN_TEST_IP).await;
+    assert_eq!(result.unwrap(), get_expected_default_response());
+}
+
+#[tokio::test]
+async fn test_client_fully() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let result = build_client(&server).query_api_fully(EXTERN_TEST_IP).await.unwrap();
+    assert_eq!(result.continent_code, "NA");
+    assert_eq!(result.reverse, "dns.google");
+    assert!(result.hosting);
+}
+
+#[tokio::test]
+async fn test_error_reserved_range() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let result = build_client(&server).query_api::<IpDefaultResponse>(TEST_IP).await;
+    match result.err().unwrap() {
+        IpApiError::ReservedRange(error_response) => {
+            assert_eq!(error_response.message, "reserved range");
+        }
+        _ => panic!("Wrong error type returned."),
+    }
+}
+
+#[tokio::test]
+async fn test_error_invalid_query() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let result = build_client(&server).query_api::<IpDefaultResponse>("Invalid Query").await;
+    match result.err().unwrap() {
+        IpApiError::InvalidQuery(error_response) => {
+            assert_eq!(error_response.message, "invalid query");
+        }
+        _ => panic!("Wrong error type returned."),
+    }
+}
+
+#[tokio::test]
+async fn test_client_batch() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let results = build_client(&server).query_api_batch::<IpDefaultResponse>(&[EXTERN_TEST_IP, TEST_IP]).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap(), &get_expected_default_response());
+    assert!(matches!(results[1], Err(IpApiError::ReservedRange(_))));
+}
+
+#[tokio::test]
+async fn test_client_bulk() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let queries = vec![EXTERN_TEST_IP; 150];
+    let results = build_client(&server).query_api_bulk::<IpDefaultResponse, _>(queries).await.unwrap();
+    assert_eq!(results.len(), 150);
+    assert!(results.iter().all(|result| result.as_ref().unwrap() == &get_expected_default_response()));
+    assert_eq!(server.request_count(), 2);
+}
+
+#[tokio::test]
+async fn test_client_bulk_failed_batch() {
+    let server = MockServer::start(build_fixtures().raw("4.4.4.4", 400, "Bad Request")).unwrap();
+    let mut queries = vec![EXTERN_TEST_IP; 150];
+    queries[120] = "4.4.4.4";
+    let results = build_client(&server).query_api_bulk::<IpDefaultResponse, _>(queries).await.unwrap();
+    assert_eq!(results.len(), 150);
+    assert!(results[..100].iter().all(|result| result.is_ok()));
+    for (index, result) in results[100..].iter().enumerate() {
+        let error = result.as_ref().unwrap_err();
+        assert_eq!(
+            error.query(),
+            Some(if index == 20 {
+                "4.4.4.4"
+            } else {
+                EXTERN_TEST_IP
+            })
+        );
+        assert!(matches!(
+            error,
+            IpApiError::BatchFailed { source, .. } if matches!(**source, IpApiError::HttpStatus { status: 400, .. })
+        ));
+    }
+}
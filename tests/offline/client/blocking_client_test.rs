@@ -0,0 +1,42 @@
+use crate::constants::{EXTERN_TEST_IP, TEST_IP};
+use crate::end2end::client::utils::get_expected_default_response;
+use crate::offline::utils::build_fixtures;
+use ip_api4rs::blocking::client::BlockingIpApiClient;
+use ip_api4rs::client::BlockingIpApi;
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::model::ip_response::IpDefaultResponse;
+use ip_api4rs::test_util::server::MockServer;
+
+fn build_client(server: &MockServer) -> BlockingIpApiClient {
+    BlockingIpApiClient::builder().base_url(server.base_url()).build_blocking().unwrap()
+}
+
+#[test]
+fn test_blocking_client() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let result = build_client(&server).query_api_default(EXTERN_TEST_IP);
+    assert_eq!(result.unwrap(), get_expected_default_response());
+}
+
+#[test]
+fn test_error_reserved_range() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let result = build_client(&server).query_api::<IpDefaultResponse>(TEST_IP);
+    assert!(matches!(result, Err(IpApiError::ReservedRange(_))));
+}
+
+#[test]
+fn test_error_invalid_query() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let result = build_client(&server).query_api::<IpDefaultResponse>("Invalid Query");
+    assert!(matches!(result, Err(IpApiError::InvalidQuery(_))));
+}
+
+#[test]
+fn test_blocking_client_batch() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let results = build_client(&server).query_api_batch::<IpDefaultResponse>(&[EXTERN_TEST_IP, TEST_IP]).unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap(), &get_expected_default_response());
+    assert!(matches!(results[1], Err(IpApiError::ReservedRange(_))));
+}
@@ -0,0 +1,47 @@
+use ip_api4rs::blocking::client::BlockingIpApiClient;
+use ip_api4rs::client::{AsyncIpApi, BlockingIpApi};
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::model::edns_response::{EdnsResponse, EdnsServer};
+use ip_api4rs::test_util::fixture::Fixtures;
+use ip_api4rs::test_util::mock_client::{MockIpApiClient, MockMethod};
+use ip_api4rs::test_util::server::MockServer;
+use ip_api4rs::IpApiClient;
+
+fn build_edns_response() -> EdnsResponse {
+    EdnsResponse {
+        dns: EdnsServer {
+            ip: "74.125.73.82".to_string(),
+            geo: "Netherlands - Google".to_string(),
+        },
+        edns: Some(EdnsServer {
+            ip: "203.0.113.0".to_string(),
+            geo: "Germany - Example ISP".to_string(),
+        }),
+    }
+}
+
+#[tokio::test]
+async fn test_edns() {
+    let server = MockServer::start(Fixtures::new().edns(build_edns_response())).unwrap();
+    let client = IpApiClient::builder().edns_base_url(server.edns_base_url()).build().unwrap();
+    assert_eq!(client.query_edns().await.unwrap(), build_edns_response());
+    assert_eq!(server.requests(), vec!["GET /edns/json"]);
+}
+
+#[test]
+fn test_blocking_edns_without_fixture() {
+    let server = MockServer::start(Fixtures::new()).unwrap();
+    let client = BlockingIpApiClient::builder().edns_base_url(server.edns_base_url()).build_blocking().unwrap();
+    let response = client.query_edns().unwrap();
+    assert_eq!(response.dns.ip, "127.0.0.1");
+    assert_eq!(response.edns, None);
+}
+
+#[tokio::test]
+async fn test_mock_client_edns() {
+    let client = MockIpApiClient::new();
+    assert!(matches!(AsyncIpApi::query_edns(&client).await, Err(IpApiError::Unknown(_))));
+    let client = client.edns(build_edns_response());
+    assert_eq!(BlockingIpApi::query_edns(&client).unwrap(), build_edns_response());
+    assert_eq!(client.calls()[1].method, MockMethod::Edns);
+}
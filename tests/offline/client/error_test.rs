@@ -0,0 +1,107 @@
+use crate::constants::EXTERN_TEST_IP;
+use ip_api4rs::client::AsyncIpApi;
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::model::ip_response::IpDefaultResponse;
+use ip_api4rs::test_util::fixture::Fixtures;
+use ip_api4rs::test_util::server::MockServer;
+use ip_api4rs::util::retry::RetryPolicy;
+use ip_api4rs::IpApiClient;
+use std::time::Duration;
+
+const SSL_UNAVAILABLE_BODY: &str =
+    r#"{"status":"fail","message":"SSL unavailable for this endpoint, order a key at https://members.ip-api.com/"}"#;
+
+fn build_fixtures() -> Fixtures {
+    Fixtures::new()
+        .fail("192.168.0.1", "private range")
+        .fail("1.1.1.1", "invalid key")
+        .fail("2.2.2.2", "quota exceeded")
+        .raw("3.3.3.3", 403, SSL_UNAVAILABLE_BODY)
+        .raw("4.4.4.4", 503, "Service Unavailable")
+        .raw("5.5.5.5", 200, "<html>Please log in</html>")
+        .fail("6.6.6.6", "something new")
+}
+
+async fn query(server: &MockServer, query: &str) -> IpApiError {
+    let client = IpApiClient::builder().base_url(server.base_url()).reserved_range_check(false).build().unwrap();
+    client.query_api::<IpDefaultResponse>(query).await.unwrap_err()
+}
+
+#[tokio::test]
+async fn test_error_responses() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let error = query(&server, "192.168.0.1").await;
+    assert!(matches!(error, IpApiError::PrivateRange(_)));
+    assert_eq!(error.query(), Some("192.168.0.1"));
+    assert!(matches!(query(&server, "1.1.1.1").await, IpApiError::InvalidKey(_)));
+    assert!(matches!(query(&server, "2.2.2.2").await, IpApiError::QuotaExceeded(_)));
+    assert!(matches!(query(&server, "6.6.6.6").await, IpApiError::Unknown(_)));
+}
+
+#[tokio::test]
+async fn test_error_status() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let error = query(&server, "3.3.3.3").await;
+    assert!(matches!(error, IpApiError::SslUnavailable(_)));
+    assert!(error.error_response().unwrap().message.starts_with("SSL unavailable"));
+    assert_eq!(error.query(), Some("3.3.3.3"));
+    let error = query(&server, "4.4.4.4").await;
+    assert!(matches!(
+        error,
+        IpApiError::HttpStatus {
+            status: 503,
+            ..
+        }
+    ));
+    assert_eq!(error.query(), Some("4.4.4.4"));
+}
+
+#[tokio::test]
+async fn test_error_non_json_body() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    match query(&server, "5.5.5.5").await {
+        IpApiError::NonJsonBody {
+            body,
+            query,
+        } => {
+            assert_eq!(body, "<html>Please log in</html>");
+            assert_eq!(query.as_deref(), Some("5.5.5.5"));
+        }
+        error => panic!("Wrong error type returned: {:?}", error),
+    }
+}
+
+#[tokio::test]
+async fn test_error_status_retried() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = IpApiClient::builder()
+        .base_url(server.base_url())
+        .retry_policy(RetryPolicy::new(3, Duration::from_millis(10)))
+        .build()
+        .unwrap();
+    let result = client.query_api_default("4.4.4.4").await;
+    assert!(matches!(
+        result,
+        Err(IpApiError::HttpStatus {
+            status: 503,
+            ..
+        })
+    ));
+    assert_eq!(server.request_count(), 3);
+    assert!(client.query_api_default("1.1.1.1").await.is_err());
+    assert_eq!(server.request_count(), 4);
+}
+
+#[tokio::test]
+async fn test_error_unknown_path() {
+    let server = MockServer::start(Fixtures::new()).unwrap();
+    let client = IpApiClient::builder().base_url(format!("{}/missing", server.base_url())).build().unwrap();
+    let result = client.query_api_default(EXTERN_TEST_IP).await;
+    assert!(matches!(
+        result,
+        Err(IpApiError::HttpStatus {
+            status: 404,
+            ..
+        })
+    ));
+}
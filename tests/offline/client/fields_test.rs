@@ -0,0 +1,64 @@
+use crate::constants::{EXTERN_TEST_IP, TEST_IP};
+use crate::offline::utils::build_fixtures;
+use ip_api4rs::blocking::client::BlockingIpApiClient;
+use ip_api4rs::client::{AsyncIpApi, BlockingIpApi};
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::field::Field;
+use ip_api4rs::format::ResponseFormat;
+use ip_api4rs::model::ip_response::IpUniversalResponse;
+use ip_api4rs::test_util::mock_client::{MockIpApiClient, MockMethod};
+use ip_api4rs::test_util::server::MockServer;
+use ip_api4rs::IpApiClient;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_query_fields() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = IpApiClient::builder().base_url(server.base_url()).build().unwrap();
+    let response = client.query_fields(EXTERN_TEST_IP, &[Field::Isp, Field::Proxy, Field::Offset]).await.unwrap();
+    assert_eq!(response.fields(), vec![Field::Offset, Field::Isp, Field::Proxy]);
+    assert_eq!(response.isp(), Some("Google LLC"));
+    assert_eq!(response.proxy(), Some(false));
+    assert_eq!(response.offset(), Some(-14400));
+    assert!(server.requests()[0].contains("fields=33718784"));
+
+    let response = client.query_fields(EXTERN_TEST_IP, &[]).await.unwrap();
+    assert_eq!(response.fields(), Field::DEFAULT.to_vec());
+    let result = client.query_fields(TEST_IP, &[Field::Country]).await;
+    assert!(matches!(result, Err(IpApiError::ReservedRange(_))));
+}
+
+#[test]
+fn test_blocking_query_fields_csv() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = BlockingIpApiClient::builder()
+        .base_url(server.base_url())
+        .response_format(ResponseFormat::Csv)
+        .build_blocking()
+        .unwrap();
+    let response = client.query_fields(EXTERN_TEST_IP, &[Field::Hosting, Field::Lat, Field::City]).unwrap();
+    assert_eq!(response.city(), Some("Ashburn"));
+    assert_eq!(response.lat(), Some(39.03));
+    assert_eq!(response.hosting(), Some(true));
+}
+
+#[test]
+fn test_mock_client_query_fields() {
+    let client =
+        MockIpApiClient::new().response(EXTERN_TEST_IP, json!({"country": "United States", "isp": "Google LLC"}));
+    let response = BlockingIpApi::query_fields(&client, EXTERN_TEST_IP, &[Field::Isp]).unwrap();
+    assert_eq!(response.fields(), vec![Field::Isp]);
+    assert_eq!(response.country(), None);
+    assert_eq!(client.calls()[0].method, MockMethod::Fields);
+}
+
+#[tokio::test]
+async fn test_query_universal_response() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = IpApiClient::builder().base_url(server.base_url()).build().unwrap();
+    let response = client.query_api::<IpUniversalResponse>(EXTERN_TEST_IP).await.unwrap();
+    assert_eq!(response.country.as_deref(), Some("United States"));
+    assert_eq!(response.isp.as_deref(), Some("Google LLC"));
+    assert_eq!(response.query.as_deref(), Some(EXTERN_TEST_IP));
+    assert!(server.requests()[0].contains("fields=66846719"));
+}
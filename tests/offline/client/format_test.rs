@@ -0,0 +1,134 @@
+use crate::constants::{EXTERN_TEST_IP, TEST_IP};
+use crate::end2end::client::utils::get_expected_default_response;
+use crate::offline::utils::build_fixtures;
+use ip_api4rs::blocking::client::BlockingIpApiClient;
+use ip_api4rs::client::{AsyncIpApi, BlockingIpApi};
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::format::{self, ResponseFormat};
+use ip_api4rs::model::ip_response::{IpDefaultResponse, IpFullResponse, IpUniversalResponse};
+use ip_api4rs::test_util::fixture::Fixtures;
+use ip_api4rs::test_util::mock_client::{MockIpApiClient, MockMethod};
+use ip_api4rs::test_util::server::MockServer;
+use ip_api4rs::IpApiClient;
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Deserialize, Debug)]
+struct Location {
+    country: String,
+    lat: f32,
+    hosting: bool,
+}
+
+fn build_client(server: &MockServer, format: ResponseFormat) -> IpApiClient {
+    IpApiClient::builder().base_url(server.base_url()).response_format(format).build().unwrap()
+}
+
+#[tokio::test]
+async fn test_formats() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    for format in ResponseFormat::ALL {
+        let client = build_client(&server, format);
+        assert_eq!(client.query_api_default(EXTERN_TEST_IP).await.unwrap(), get_expected_default_response());
+        let response = client.query_api_fully(EXTERN_TEST_IP).await.unwrap();
+        assert_eq!(response.as_name, "GOOGLE");
+        let location = client.query_api::<Location>(EXTERN_TEST_IP).await.unwrap();
+        assert_eq!((location.country.as_str(), location.lat, location.hosting), ("United States", 39.03, true));
+        let result = client.query_api_default(TEST_IP).await;
+        assert!(matches!(result, Err(IpApiError::ReservedRange(_))));
+    }
+    let requests = server.requests();
+    assert!(requests.iter().any(|request| request.starts_with("GET /csv/8.8.8.8?")));
+    assert!(requests.iter().any(|request| request.starts_with("GET /xml/8.8.8.8?")));
+}
+
+#[tokio::test]
+async fn test_raw() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = build_client(&server, ResponseFormat::Json);
+    let body = client.query_api_raw::<Location>(EXTERN_TEST_IP, ResponseFormat::Csv).await.unwrap();
+    assert_eq!(body, "\"United States\",39.03,true");
+    let body = client.query_api_raw::<IpDefaultResponse>(EXTERN_TEST_IP, ResponseFormat::Line).await.unwrap();
+    assert_eq!(body.lines().last(), Some(EXTERN_TEST_IP));
+    let result = client.query_api_raw::<IpDefaultResponse>(TEST_IP, ResponseFormat::Csv).await;
+    assert!(matches!(result, Err(IpApiError::ReservedRange(_))));
+}
+
+#[test]
+fn test_blocking_formats() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = BlockingIpApiClient::builder()
+        .base_url(server.base_url())
+        .response_format(ResponseFormat::Line)
+        .build_blocking()
+        .unwrap();
+    assert_eq!(client.query_api_default(EXTERN_TEST_IP).unwrap(), get_expected_default_response());
+    let body = client.query_api_raw::<IpFullResponse>(EXTERN_TEST_IP, ResponseFormat::Xml).unwrap();
+    assert!(body.contains("<asname><![CDATA[GOOGLE]]></asname>"));
+}
+
+#[test]
+fn test_mock_client_raw() {
+    let client = MockIpApiClient::new().response(EXTERN_TEST_IP, json!({"country": "United States", "lat": 39.03}));
+    let body = BlockingIpApi::query_api_raw::<Location>(&client, EXTERN_TEST_IP, ResponseFormat::Csv);
+    assert_eq!(body.unwrap(), "\"United States\",39.03");
+    assert_eq!(client.calls()[0].method, MockMethod::Raw);
+}
+
+/// Encodes a successful response with every field but `message`, which the API leaves out on success.
+fn encode_without_message(format: ResponseFormat) -> String {
+    let response = json!({
+        "status": "success",
+        "continent": "North America",
+        "continentCode": "NA",
+        "country": "United States",
+        "countryCode": "US",
+        "region": "VA",
+        "regionName": "Virginia",
+        "city": "Ashburn",
+        "district": "",
+        "zip": "20149",
+        "lat": 39.03,
+        "lon": -77.5,
+        "timezone": "America/New_York",
+        "offset": -14400,
+        "currency": "USD",
+        "isp": "Google LLC",
+        "org": "Google Public DNS",
+        "as": "AS15169 Google LLC",
+        "asname": "GOOGLE",
+        "reverse": "dns.google",
+        "mobile": false,
+        "proxy": false,
+        "hosting": true,
+        "query": EXTERN_TEST_IP,
+    });
+    format::encode(format, response.as_object().unwrap())
+}
+
+#[tokio::test]
+async fn test_csv_without_message_column() {
+    let fixtures = Fixtures::new().raw(EXTERN_TEST_IP, 200, encode_without_message(ResponseFormat::Csv));
+    let server = MockServer::start(fixtures).unwrap();
+    let client = build_client(&server, ResponseFormat::Csv);
+    let response = client.query_api::<IpUniversalResponse>(EXTERN_TEST_IP).await.unwrap();
+    assert_eq!(response.country.as_deref(), Some("United States"));
+    assert_eq!(response.district, None);
+    assert_eq!(response.message, None);
+    assert_eq!(response.query.as_deref(), Some(EXTERN_TEST_IP));
+}
+
+#[test]
+fn test_line_without_message_column() {
+    let fixtures = Fixtures::new().raw(EXTERN_TEST_IP, 200, encode_without_message(ResponseFormat::Line));
+    let server = MockServer::start(fixtures).unwrap();
+    let client = BlockingIpApiClient::builder()
+        .base_url(server.base_url())
+        .response_format(ResponseFormat::Line)
+        .build_blocking()
+        .unwrap();
+    let response = client.query_api::<IpUniversalResponse>(EXTERN_TEST_IP).unwrap();
+    assert_eq!(response.lat, Some(39.03));
+    assert_eq!(response.hosting, Some(true));
+    assert_eq!(response.message, None);
+}
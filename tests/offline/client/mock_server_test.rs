@@ -0,0 +1,68 @@
+use crate::constants::EXTERN_TEST_IP;
+use crate::offline::utils::build_fixtures;
+use ip_api4rs::blocking::client::BlockingIpApiClient;
+use ip_api4rs::client::{AsyncIpApi, BlockingIpApi, IpApi};
+use ip_api4rs::constant::MAX_RATE_LIMIT_WAITS;
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::test_util::server::MockServer;
+use ip_api4rs::util::rate_limit::RateLimitHandling;
+use ip_api4rs::IpApiClient;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_field_filtering() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let url = format!("{}/json/{}?fields=country,query,unknown", server.base_url(), EXTERN_TEST_IP);
+    let response = reqwest::get(url).await.unwrap().json::<serde_json::Value>().await.unwrap();
+    assert_eq!(response, serde_json::json!({"country": "United States", "query": EXTERN_TEST_IP}));
+    let url = format!("{}/json/{}?fields=16896", server.base_url(), EXTERN_TEST_IP);
+    let response = reqwest::get(url).await.unwrap().json::<serde_json::Value>().await.unwrap();
+    assert_eq!(response, serde_json::json!({"status": "success", "isp": "Google LLC"}));
+}
+
+#[tokio::test]
+async fn test_rate_limit_headers() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = IpApiClient::builder().base_url(server.base_url()).build().unwrap();
+    client.query_api_default(EXTERN_TEST_IP).await.unwrap();
+    assert_eq!(client.get_server_rate_limit().remaining(), Some(44));
+    client.query_api_batch::<serde_json::Value>(&[EXTERN_TEST_IP]).await.unwrap();
+    assert_eq!(client.get_batch_server_rate_limit().remaining(), Some(14));
+}
+
+#[tokio::test]
+async fn test_rate_limit_exceeded() {
+    let server = MockServer::start_with_rate_limit(build_fixtures(), 1, 1, Duration::from_secs(60)).unwrap();
+    let url = format!("{}/json/{}", server.base_url(), EXTERN_TEST_IP);
+    assert_eq!(reqwest::get(&url).await.unwrap().status(), 200);
+    let response = reqwest::get(&url).await.unwrap();
+    assert_eq!(response.status(), 429);
+    assert_eq!(response.headers()["X-Rl"], "0");
+    assert_eq!(response.headers()["X-Ttl"], "60");
+
+    let client = IpApiClient::builder()
+        .base_url(server.base_url())
+        .rate_limit_handling(RateLimitHandling::ReturnError)
+        .build()
+        .unwrap();
+    let result = client.query_api_default(EXTERN_TEST_IP).await;
+    assert!(matches!(result, Err(IpApiError::RateLimited { .. })));
+}
+
+#[tokio::test]
+async fn test_rate_limit_wait_is_bounded() {
+    let server = MockServer::start_with_rate_limit(build_fixtures(), 0, 0, Duration::from_secs(1)).unwrap();
+    let client = IpApiClient::builder().base_url(server.base_url()).build().unwrap();
+    let result = client.query_api_default(EXTERN_TEST_IP).await;
+    assert!(matches!(result, Err(IpApiError::RateLimited { .. })));
+    assert_eq!(server.request_count(), 1 + MAX_RATE_LIMIT_WAITS as usize);
+}
+
+#[test]
+fn test_blocking_rate_limit_wait_is_bounded() {
+    let server = MockServer::start_with_rate_limit(build_fixtures(), 0, 0, Duration::from_secs(1)).unwrap();
+    let client = BlockingIpApiClient::builder().base_url(server.base_url()).build_blocking().unwrap();
+    let result = client.query_api_batch::<serde_json::Value>(&[EXTERN_TEST_IP]);
+    assert!(matches!(result, Err(IpApiError::RateLimited { .. })));
+    assert_eq!(server.request_count(), 1 + MAX_RATE_LIMIT_WAITS as usize);
+}
@@ -0,0 +1,9 @@
+pub mod async_client_test;
+pub mod blocking_client_test;
+pub mod edns_test;
+pub mod error_test;
+pub mod fields_test;
+pub mod format_test;
+pub mod mock_server_test;
+pub mod query_test;
+pub mod reserved_range_test;
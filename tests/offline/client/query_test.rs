@@ -0,0 +1,102 @@
+use crate::constants::EXTERN_TEST_IP;
+use crate::end2end::client::utils::get_expected_default_response;
+use crate::offline::utils::build_fixtures;
+use ip_api4rs::blocking::client::BlockingIpApiClient;
+use ip_api4rs::client::{AsyncIpApi, BlockingIpApi};
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::model::ip_response::IpDefaultResponse;
+use ip_api4rs::query::Query;
+use ip_api4rs::test_util::fixture::Fixtures;
+use ip_api4rs::test_util::server::MockServer;
+use ip_api4rs::IpApiClient;
+use serde::Deserialize;
+use serde_json::json;
+use std::net::{IpAddr, Ipv4Addr};
+
+#[derive(Deserialize)]
+struct CountryResponse {
+    country: String,
+}
+
+#[derive(Deserialize)]
+struct Location {
+    lat: f32,
+    lon: f32,
+}
+
+#[derive(Deserialize)]
+struct FlattenedResponse {
+    country: String,
+    #[serde(flatten)]
+    location: Location,
+}
+
+#[tokio::test]
+async fn test_typed_queries() {
+    let fixtures = build_fixtures().success("dns.google", json!({"country": "United States"}));
+    let server = MockServer::start(fixtures).unwrap();
+    let client = IpApiClient::builder().base_url(server.base_url()).build().unwrap();
+    let ip = IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8));
+    assert_eq!(client.query_api_default(ip).await.unwrap(), get_expected_default_response());
+    assert_eq!(client.query_api_default(Query::parse(EXTERN_TEST_IP).unwrap()).await.unwrap().query, EXTERN_TEST_IP);
+    let response = client.query_api::<CountryResponse>(Query::host("dns.google").unwrap()).await.unwrap();
+    assert_eq!(response.country, "United States");
+    assert_eq!(server.request_count(), 3);
+}
+
+#[tokio::test]
+async fn test_flattened_query() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = IpApiClient::builder().base_url(server.base_url()).build().unwrap();
+    let response = client.query_api::<FlattenedResponse>(EXTERN_TEST_IP).await.unwrap();
+    assert_eq!(response.country, "United States");
+    assert_eq!((response.location.lat, response.location.lon), (39.03, -77.5));
+    assert_eq!(server.requests(), vec![format!("GET /json/{}?fields=32961", EXTERN_TEST_IP)]);
+}
+
+#[tokio::test]
+async fn test_invalid_query_not_sent() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = IpApiClient::builder().base_url(server.base_url()).build().unwrap();
+    assert!(matches!(client.query_api_default("Invalid Query").await, Err(IpApiError::InvalidQuery(_))));
+    let results = client.query_api_batch::<IpDefaultResponse>(&["Invalid Query", EXTERN_TEST_IP]).await.unwrap();
+    assert!(matches!(results[0], Err(IpApiError::InvalidQuery(_))));
+    assert!(results[1].is_ok());
+    assert_eq!(server.request_count(), 1);
+}
+
+#[tokio::test]
+async fn test_batch_of_ip_addresses() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = IpApiClient::builder().base_url(server.base_url()).build().unwrap();
+    let ip: IpAddr = EXTERN_TEST_IP.parse().unwrap();
+    let results = client.query_api_batch::<IpDefaultResponse>(&[ip]).await.unwrap();
+    assert_eq!(results[0].as_ref().unwrap(), &get_expected_default_response());
+    let results = client.query_api_bulk::<IpDefaultResponse, _>(vec![ip, ip]).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(Result::is_ok));
+}
+
+#[test]
+fn test_blocking_bulk_of_queries() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = BlockingIpApiClient::builder().base_url(server.base_url()).build_blocking().unwrap();
+    let ip: Ipv4Addr = EXTERN_TEST_IP.parse().unwrap();
+    let queries = vec![Query::from(ip)];
+    let results = client.query_api_bulk::<IpDefaultResponse, _>(&queries).unwrap();
+    assert!(results[0].is_ok());
+    let results =
+        client.query_api_bulk::<IpDefaultResponse, _>([EXTERN_TEST_IP.to_string(), "Invalid Query".to_string()]);
+    let results = results.unwrap();
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(IpApiError::InvalidQuery(_))));
+}
+
+#[test]
+fn test_self_query() {
+    let server = MockServer::start(Fixtures::new().success("127.0.0.1", get_expected_default_response())).unwrap();
+    let client = BlockingIpApiClient::builder().base_url(server.base_url()).build_blocking().unwrap();
+    let response = client.query_api_default(Query::SelfIp).unwrap();
+    assert_eq!(response.country, "United States");
+    assert!(server.requests()[0].starts_with("GET /json/?"));
+}
@@ -0,0 +1,54 @@
+use crate::constants::{EXTERN_TEST_IP, TEST_IP};
+use crate::end2end::client::utils::get_expected_default_response;
+use crate::offline::utils::build_fixtures;
+use ip_api4rs::blocking::client::BlockingIpApiClient;
+use ip_api4rs::client::{AsyncIpApi, BlockingIpApi};
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::model::ip_response::IpDefaultResponse;
+use ip_api4rs::test_util::server::MockServer;
+use ip_api4rs::IpApiClient;
+
+#[tokio::test]
+async fn test_reserved_range_not_sent() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = IpApiClient::builder().base_url(server.base_url()).build().unwrap();
+    assert!(matches!(client.query_api_default(TEST_IP).await, Err(IpApiError::ReservedRange(_))));
+    assert!(matches!(client.query_api_default("10.0.0.1").await, Err(IpApiError::PrivateRange(_))));
+    assert_eq!(server.request_count(), 0);
+}
+
+#[tokio::test]
+async fn test_reserved_range_check_disabled() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = IpApiClient::builder().base_url(server.base_url()).reserved_range_check(false).build().unwrap();
+    assert!(matches!(client.query_api_default(TEST_IP).await, Err(IpApiError::ReservedRange(_))));
+    assert_eq!(server.request_count(), 1);
+}
+
+#[tokio::test]
+async fn test_batch_with_reserved_ranges() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = IpApiClient::builder().base_url(server.base_url()).build().unwrap();
+    let queries = [TEST_IP, EXTERN_TEST_IP, "192.168.0.1", EXTERN_TEST_IP];
+    let results = client.query_api_batch::<IpDefaultResponse>(&queries).await.unwrap();
+    assert_eq!(results.len(), 4);
+    assert!(matches!(results[0], Err(IpApiError::ReservedRange(_))));
+    assert_eq!(results[1].as_ref().unwrap(), &get_expected_default_response());
+    assert!(matches!(results[2], Err(IpApiError::PrivateRange(_))));
+    assert_eq!(results[3].as_ref().unwrap(), &get_expected_default_response());
+    assert_eq!(server.request_count(), 1);
+    let results = client.query_api_batch::<IpDefaultResponse>(&[TEST_IP]).await.unwrap();
+    assert!(matches!(results[0], Err(IpApiError::ReservedRange(_))));
+    assert_eq!(server.request_count(), 1);
+}
+
+#[test]
+fn test_blocking_batch_with_reserved_ranges() {
+    let server = MockServer::start(build_fixtures()).unwrap();
+    let client = BlockingIpApiClient::builder().base_url(server.base_url()).build_blocking().unwrap();
+    let results = client.query_api_batch::<IpDefaultResponse>(&["10.0.0.1", EXTERN_TEST_IP]).unwrap();
+    assert!(matches!(results[0], Err(IpApiError::PrivateRange(_))));
+    assert_eq!(results[1].as_ref().unwrap(), &get_expected_default_response());
+    assert!(matches!(client.query_api_default("::1"), Err(IpApiError::ReservedRange(_))));
+    assert_eq!(server.request_count(), 1);
+}
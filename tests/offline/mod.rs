@@ -0,0 +1,3 @@
+pub mod client;
+pub mod monitor_test;
+pub mod utils;
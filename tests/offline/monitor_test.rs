@@ -0,0 +1,94 @@
+use ip_api4rs::blocking::client::BlockingIpApiClient;
+use ip_api4rs::client::{AsyncIpApi, BlockingIpApi};
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::monitor::{ChangedProperty, MonitorEvent, PublicIpInfo, PublicIpMonitor};
+use ip_api4rs::test_util::fixture::Fixtures;
+use ip_api4rs::test_util::mock_client::{MockIpApiClient, MockMethod};
+use ip_api4rs::test_util::server::MockServer;
+use ip_api4rs::IpApiClient;
+use serde_json::json;
+use std::sync::mpsc;
+use std::time::Duration;
+
+const LOOPBACK: &str = "127.0.0.1";
+
+fn build_info(ip: &str, isp: &str, country_code: &str) -> PublicIpInfo {
+    PublicIpInfo {
+        ip: ip.to_string(),
+        status: "success".to_string(),
+        isp: isp.to_string(),
+        country: country_code.to_string(),
+        country_code: country_code.to_string(),
+    }
+}
+
+fn build_server() -> MockServer {
+    let fixtures =
+        Fixtures::new().success(LOOPBACK, json!({"isp": "Example ISP", "country": "Germany", "countryCode": "DE"}));
+    MockServer::start(fixtures).unwrap()
+}
+
+#[tokio::test]
+async fn test_query_self() {
+    let server = build_server();
+    let client = IpApiClient::builder().base_url(server.base_url()).build().unwrap();
+    let info = client.query_self::<PublicIpInfo>().await.unwrap();
+    assert_eq!(info.ip, LOOPBACK);
+    assert_eq!(info.country, "Germany");
+    assert!(server.requests()[0].starts_with("GET /json/?"));
+}
+
+#[test]
+fn test_query_self_blocking() {
+    let server = build_server();
+    let client = BlockingIpApiClient::builder().base_url(server.base_url()).build_blocking().unwrap();
+    let info = client.query_self::<PublicIpInfo>().unwrap();
+    assert_eq!(info.ip, LOOPBACK);
+}
+
+#[test]
+fn test_interval_within_quota() {
+    let result = PublicIpMonitor::new(MockIpApiClient::new(), Duration::from_secs(1));
+    assert!(matches!(result, Err(IpApiError::InvalidConfiguration(_))));
+    assert!(PublicIpMonitor::new(MockIpApiClient::new(), Duration::from_secs(2)).is_ok());
+}
+
+#[test]
+fn test_observe_changes() {
+    let mut monitor = PublicIpMonitor::new(MockIpApiClient::new(), Duration::from_secs(60)).unwrap();
+    let first = monitor.observe(build_info("1.2.3.4", "ISP A", "DE")).unwrap();
+    assert_eq!(first.previous, None);
+    assert!(first.changed.is_empty());
+
+    assert_eq!(monitor.observe(build_info("1.2.3.4", "ISP A", "DE")), None);
+
+    let change = monitor.observe(build_info("5.6.7.8", "ISP B", "DE")).unwrap();
+    assert_eq!(change.previous, Some(build_info("1.2.3.4", "ISP A", "DE")));
+    assert_eq!(change.changed, vec![ChangedProperty::Ip, ChangedProperty::Isp]);
+
+    let change = monitor.observe(build_info("5.6.7.8", "ISP B", "AT")).unwrap();
+    assert_eq!(change.changed, vec![ChangedProperty::Country]);
+    assert_eq!(monitor.last(), Some(&build_info("5.6.7.8", "ISP B", "AT")));
+}
+
+#[tokio::test]
+async fn test_next_event() {
+    let client = MockIpApiClient::new().response("", build_info("1.2.3.4", "ISP A", "DE"));
+    let mut monitor = PublicIpMonitor::new(client, Duration::from_secs(60)).unwrap();
+    match monitor.next_event().await {
+        MonitorEvent::Changed(change) => assert_eq!(change.current.ip, "1.2.3.4"),
+        MonitorEvent::Error(error) => panic!("unexpected error: {}", error),
+    }
+    assert_eq!(monitor.client().calls()[0].method, MockMethod::SelfIp);
+}
+
+#[test]
+fn test_spawn() {
+    let client = MockIpApiClient::new().fail("", "quota exceeded");
+    let (sender, receiver) = mpsc::channel();
+    let handle =
+        PublicIpMonitor::new(client, Duration::from_secs(60)).unwrap().spawn(move |event| sender.send(event).unwrap());
+    let event = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(matches!(event, MonitorEvent::Error(IpApiError::QuotaExceeded(_))));
+    handle.stop();
+}
@@ -0,0 +1,35 @@
+use crate::constants::{EXTERN_TEST_IP, TEST_IP};
+use ip_api4rs::test_util::fixture::Fixtures;
+use serde_json::json;
+
+pub fn build_fixtures() -> Fixtures {
+    Fixtures::new()
+        .success(
+            EXTERN_TEST_IP,
+            json!({
+                "continent": "North America",
+                "continentCode": "NA",
+                "country": "United States",
+                "countryCode": "US",
+                "region": "VA",
+                "regionName": "Virginia",
+                "city": "Ashburn",
+                "district": "",
+                "zip": "20149",
+                "lat": 39.03,
+                "lon": -77.5,
+                "timezone": "America/New_York",
+                "offset": -14400,
+                "currency": "USD",
+                "isp": "Google LLC",
+                "org": "Google Public DNS",
+                "as": "AS15169 Google LLC",
+                "asname": "GOOGLE",
+                "reverse": "dns.google",
+                "mobile": false,
+                "proxy": false,
+                "hosting": true,
+            }),
+        )
+        .reserved_range(TEST_IP)
+}
@@ -1,3 +1,5 @@
 pub mod constants;
 pub mod end2end;
+#[cfg(feature = "test-util")]
+pub mod offline;
 pub mod unit;
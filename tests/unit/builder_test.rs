@@ -0,0 +1,103 @@
+use crate::constants::TEST_API_KEY;
+use governor::Quota;
+#[cfg(feature = "blocking")]
+use ip_api4rs::blocking::client::BlockingIpApiClient;
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::language::Language;
+use ip_api4rs::util::retry::RetryPolicy;
+use ip_api4rs::IpApiClient;
+use nonzero_ext::nonzero;
+use std::time::Duration;
+
+#[test]
+fn test_default_free_tier() {
+    let client = IpApiClient::builder().build().unwrap();
+    assert_eq!(client.api_key, None);
+    assert!(client.limiter.is_some());
+    assert!(client.batch_limiter.is_some());
+}
+
+#[test]
+fn test_default_pro_tier() {
+    let client = IpApiClient::builder().api_key(TEST_API_KEY).https(true).build().unwrap();
+    assert_eq!(client.api_key.as_deref(), Some(TEST_API_KEY));
+    assert!(client.limiter.is_none());
+    assert!(client.batch_limiter.is_none());
+}
+
+#[test]
+fn test_custom_settings() {
+    let client = IpApiClient::builder()
+        .quota(Quota::per_minute(nonzero!(10u32)))
+        .timeout(Duration::from_secs(5))
+        .user_agent("ip-api4rs-test")
+        .base_url("http://localhost:8080")
+        .language(Language::Japanese)
+        .build()
+        .unwrap();
+    assert!(client.limiter.is_some());
+    assert_eq!(client.base_url.as_deref(), Some("http://localhost:8080"));
+    assert_eq!(client.language, Some(Language::Japanese));
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn test_without_rate_limit() {
+    let client = BlockingIpApiClient::builder().without_rate_limit().build_blocking().unwrap();
+    assert!(client.limiter.is_none());
+    assert!(client.batch_limiter.is_none());
+}
+
+#[test]
+fn test_https_without_key() {
+    let result = IpApiClient::builder().https(true).build();
+    assert!(matches!(result, Err(IpApiError::InvalidConfiguration(_))));
+    let result = IpApiClient::builder().https(true).base_url("http://localhost:8080").build();
+    assert!(matches!(result, Err(IpApiError::InvalidConfiguration(_))));
+    let result = IpApiClient::builder().https(true).base_url("https://localhost:8080").build();
+    assert!(result.is_ok());
+    let result = IpApiClient::builder()
+        .https(true)
+        .base_url("https://localhost:8080")
+        .edns_base_url("http://localhost:8080")
+        .build();
+    assert!(matches!(result, Err(IpApiError::InvalidConfiguration(_))));
+}
+
+#[test]
+fn test_invalid_base_url() {
+    let result = IpApiClient::builder().base_url("localhost:8080").build();
+    assert!(matches!(result, Err(IpApiError::InvalidConfiguration(_))));
+    let result = IpApiClient::builder().edns_base_url("localhost:8080").build();
+    assert!(matches!(result, Err(IpApiError::InvalidConfiguration(_))));
+}
+
+#[test]
+fn test_invalid_api_key() {
+    let result = IpApiClient::builder().api_key("").build();
+    assert!(matches!(result, Err(IpApiError::InvalidConfiguration(_))));
+    let result = IpApiClient::builder().api_key("key&fields=query").build();
+    assert!(matches!(result, Err(IpApiError::InvalidConfiguration(_))));
+}
+
+#[test]
+fn test_custom_http_client_with_timeout() {
+    let result = IpApiClient::builder().http_client(reqwest::Client::new()).timeout(Duration::from_secs(5)).build();
+    assert!(matches!(result, Err(IpApiError::InvalidConfiguration(_))));
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn test_custom_blocking_http_client_with_user_agent() {
+    let result = BlockingIpApiClient::builder()
+        .blocking_http_client(reqwest::blocking::Client::new())
+        .user_agent("ip-api4rs")
+        .build_blocking();
+    assert!(matches!(result, Err(IpApiError::InvalidConfiguration(_))));
+}
+
+#[test]
+fn test_retry_policy_without_attempts() {
+    let result = IpApiClient::builder().retry_policy(RetryPolicy::new(0, Duration::from_millis(10))).build();
+    assert!(matches!(result, Err(IpApiError::InvalidConfiguration(_))));
+}
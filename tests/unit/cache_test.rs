@@ -0,0 +1,189 @@
+use crate::constants::EXTERN_TEST_IP;
+use crate::end2end::client::utils::get_expected_default_response;
+use ip_api4rs::blocking::client::BlockingIpApiClient;
+use ip_api4rs::cache::file::FileCache;
+use ip_api4rs::cache::memory::MemoryCache;
+use ip_api4rs::cache::{CacheKey, ResponseCache};
+use ip_api4rs::client::{AsyncIpApi, BlockingIpApi};
+use ip_api4rs::language::Language;
+use ip_api4rs::model::ip_response::{IpDefaultResponse, IpFullResponse};
+use ip_api4rs::IpApiClient;
+use std::path::PathBuf;
+use std::thread::sleep;
+use std::time::Duration;
+
+/// A base url nothing listens on, so every request that is not answered from the cache fails.
+const UNREACHABLE_BASE_URL: &str = "http://127.0.0.1:9";
+
+fn build_key(query: &str) -> CacheKey {
+    CacheKey::from_struct::<IpDefaultResponse>(query, None)
+}
+
+/// Gets a fresh directory for a file cache test.
+fn build_cache_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("ip-api4rs-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&directory);
+    directory
+}
+
+#[test]
+fn test_cache_key() {
+    let key = build_key(EXTERN_TEST_IP);
+    assert_eq!(key.fields, "61439");
+    assert_ne!(key, CacheKey::from_struct::<IpFullResponse>(EXTERN_TEST_IP, None));
+    assert_ne!(key, CacheKey::from_struct::<IpDefaultResponse>(EXTERN_TEST_IP, Some(Language::German)));
+}
+
+#[test]
+fn test_get_and_insert() {
+    let cache = MemoryCache::new(Duration::from_secs(60), 10);
+    assert_eq!(cache.get(&build_key("1.1.1.1")), None);
+    cache.insert(build_key("1.1.1.1"), "one".to_string());
+    assert_eq!(cache.get(&build_key("1.1.1.1")), Some("one".to_string()));
+    cache.insert(build_key("1.1.1.1"), "updated".to_string());
+    assert_eq!(cache.get(&build_key("1.1.1.1")), Some("updated".to_string()));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_expiry() {
+    let cache = MemoryCache::new(Duration::from_millis(10), 10);
+    cache.insert(build_key("1.1.1.1"), "one".to_string());
+    sleep(Duration::from_millis(20));
+    assert_eq!(cache.get(&build_key("1.1.1.1")), None);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_unbounded_ttl() {
+    let cache = MemoryCache::new(Duration::MAX, 10);
+    cache.insert(build_key("1.1.1.1"), "one".to_string());
+    assert_eq!(cache.get(&build_key("1.1.1.1")), Some("one".to_string()));
+}
+
+#[test]
+fn test_least_recently_used_eviction() {
+    let cache = MemoryCache::new(Duration::from_secs(60), 2);
+    cache.insert(build_key("1.1.1.1"), "one".to_string());
+    cache.insert(build_key("2.2.2.2"), "two".to_string());
+    assert!(cache.get(&build_key("1.1.1.1")).is_some());
+    cache.insert(build_key("3.3.3.3"), "three".to_string());
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&build_key("1.1.1.1")).is_some());
+    assert_eq!(cache.get(&build_key("2.2.2.2")), None);
+    assert!(cache.get(&build_key("3.3.3.3")).is_some());
+}
+
+#[tokio::test]
+async fn test_client_cache_hit() {
+    let client =
+        IpApiClient::builder().base_url(UNREACHABLE_BASE_URL).cache(Duration::from_secs(60), 10).build().unwrap();
+    let json = serde_json::to_string(&get_expected_default_response()).unwrap();
+    client.cache.as_ref().unwrap().insert(build_key(EXTERN_TEST_IP), json);
+    let result = client.query_api_default(EXTERN_TEST_IP).await;
+    assert_eq!(result.unwrap(), get_expected_default_response());
+    assert!(client.query_api_fully(EXTERN_TEST_IP).await.is_err());
+}
+
+#[test]
+fn test_blocking_client_cache_hit() {
+    let client = BlockingIpApiClient::builder()
+        .base_url(UNREACHABLE_BASE_URL)
+        .cache(Duration::from_secs(60), 10)
+        .build_blocking()
+        .unwrap();
+    let json = serde_json::to_string(&get_expected_default_response()).unwrap();
+    client.cache.as_ref().unwrap().insert(build_key(EXTERN_TEST_IP), json);
+    let result = client.query_api::<IpDefaultResponse>(EXTERN_TEST_IP);
+    assert_eq!(result.unwrap(), get_expected_default_response());
+}
+
+#[test]
+fn test_file_cache_survives_restart() {
+    let directory = build_cache_directory("restart");
+    let cache = FileCache::new(&directory, Duration::from_secs(60)).unwrap();
+    assert_eq!(cache.get(&build_key("1.1.1.1")), None);
+    cache.insert(build_key("1.1.1.1"), "one".to_string());
+    drop(cache);
+    let cache = FileCache::new(&directory, Duration::from_secs(60)).unwrap();
+    assert_eq!(cache.get(&build_key("1.1.1.1")), Some("one".to_string()));
+    assert_eq!(cache.get(&build_key("2.2.2.2")), None);
+    cache.clear().unwrap();
+    assert_eq!(cache.get(&build_key("1.1.1.1")), None);
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_file_cache_expiry() {
+    let directory = build_cache_directory("expiry");
+    let cache = FileCache::new(&directory, Duration::ZERO).unwrap();
+    cache.insert(build_key("1.1.1.1"), "one".to_string());
+    assert_eq!(cache.get(&build_key("1.1.1.1")), None);
+    assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 0);
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_file_cache_sub_second_ttl() {
+    let directory = build_cache_directory("sub_second");
+    let cache = FileCache::new(&directory, Duration::from_millis(500)).unwrap();
+    cache.insert(build_key("1.1.1.1"), "one".to_string());
+    assert_eq!(cache.get(&build_key("1.1.1.1")), Some("one".to_string()));
+    sleep(Duration::from_millis(600));
+    assert_eq!(cache.get(&build_key("1.1.1.1")), None);
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_file_cache_long_query() {
+    let directory = build_cache_directory("long_query");
+    let cache = FileCache::new(&directory, Duration::from_secs(60)).unwrap();
+    let query = format!("{}.example.com", "a".repeat(240));
+    assert_eq!(build_key(&query).to_file_name().len(), 64);
+    cache.insert(build_key(&query), "long".to_string());
+    assert_eq!(cache.get(&build_key(&query)), Some("long".to_string()));
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_file_cache_failed_write() {
+    let directory = build_cache_directory("failed_write");
+    let cache = FileCache::new(&directory, Duration::from_secs(60)).unwrap();
+    let path = directory.join(format!("{}.json", build_key("1.1.1.1").to_file_name()));
+    std::fs::create_dir(&path).unwrap();
+    cache.insert(build_key("1.1.1.1"), "one".to_string());
+    assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 1);
+    assert!(path.is_dir());
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_file_cache_concurrent_writes() {
+    let directory = build_cache_directory("concurrent");
+    let cache = FileCache::new(&directory, Duration::from_secs(60)).unwrap();
+    std::thread::scope(|scope| {
+        for index in 0..8 {
+            let cache = &cache;
+            scope.spawn(move || {
+                for _ in 0..20 {
+                    cache.insert(build_key("1.1.1.1"), format!("writer {}", index));
+                }
+            });
+        }
+    });
+    assert!(cache.get(&build_key("1.1.1.1")).is_some_and(|body| body.starts_with("writer ")));
+    assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 1);
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[tokio::test]
+async fn test_client_file_cache_hit() {
+    let directory = build_cache_directory("client");
+    let cache = FileCache::new(&directory, Duration::from_secs(60)).unwrap();
+    let json = serde_json::to_string(&get_expected_default_response()).unwrap();
+    cache.insert(build_key(EXTERN_TEST_IP), json);
+    let client = IpApiClient::builder().base_url(UNREACHABLE_BASE_URL).response_cache(cache).build().unwrap();
+    let result = client.query_api_default(EXTERN_TEST_IP).await;
+    assert_eq!(result.unwrap(), get_expected_default_response());
+    std::fs::remove_dir_all(directory).unwrap();
+}
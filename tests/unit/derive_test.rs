@@ -0,0 +1,119 @@
+use ip_api4rs::field::{Field, FieldSet};
+use ip_api4rs::model::ip_api_model::IpApiModel;
+use ip_api4rs::util::urls::build_fields_param_from_struct;
+use serde::Deserialize;
+
+#[derive(Deserialize, IpApiModel)]
+#[allow(dead_code)]
+struct Renamed {
+    #[serde(rename = "query")]
+    ip: String,
+    #[serde(rename = "countryCode")]
+    country_code: String,
+    #[serde(rename = "as")]
+    asn: String,
+}
+
+#[derive(Deserialize, IpApiModel)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct CamelCase {
+    region_name: String,
+    continent_code: String,
+    #[serde(rename(deserialize = "asname"))]
+    as_name: String,
+    r#as: String,
+}
+
+#[derive(Deserialize, IpApiModel)]
+#[allow(dead_code)]
+struct Skipped {
+    lat: f32,
+    lon: f32,
+    #[serde(skip)]
+    distance: f32,
+    #[serde(default, skip_deserializing)]
+    label: String,
+}
+
+#[derive(Deserialize, IpApiModel)]
+struct Empty {}
+
+#[derive(Deserialize, IpApiModel)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct Everything {
+    status: String,
+    message: String,
+    continent: String,
+    continent_code: String,
+    country: String,
+    country_code: String,
+    region: String,
+    region_name: String,
+    city: String,
+    district: String,
+    zip: String,
+    lat: f32,
+    lon: f32,
+    timezone: String,
+    offset: i32,
+    currency: String,
+    isp: String,
+    org: String,
+    r#as: String,
+    #[serde(rename = "asname")]
+    as_name: String,
+    reverse: String,
+    mobile: bool,
+    proxy: bool,
+    hosting: bool,
+    query: String,
+}
+
+#[test]
+fn test_derive_renamed_fields() {
+    assert_eq!(Renamed::FIELDS, Field::Query | Field::CountryCode | Field::As);
+    assert_eq!(CamelCase::FIELDS, Field::RegionName | Field::ContinentCode | Field::AsName | Field::As);
+}
+
+#[test]
+fn test_derive_skipped_fields() {
+    assert_eq!(Skipped::FIELDS, Field::Lat | Field::Lon);
+    assert_eq!(Empty::FIELDS, FieldSet::empty());
+}
+
+#[test]
+fn test_derive_all_fields() {
+    assert_eq!(Everything::FIELDS, Field::ALL.into_iter().collect::<FieldSet>());
+}
+
+#[test]
+fn test_derive_fields_param() {
+    assert_eq!(Renamed::FIELDS_PARAM, build_fields_param_from_struct::<Renamed>());
+    assert_eq!(CamelCase::FIELDS_PARAM, build_fields_param_from_struct::<CamelCase>());
+    assert_eq!(Empty::FIELDS_PARAM, Field::Message.bit().to_string());
+}
+
+#[derive(Deserialize, IpApiModel)]
+#[allow(dead_code)]
+struct Location {
+    lat: f32,
+    lon: f32,
+}
+
+#[derive(Deserialize, IpApiModel)]
+#[allow(dead_code)]
+struct Flattened {
+    #[serde(flatten)]
+    location: Location,
+    #[serde(flatten)]
+    renamed: Option<Renamed>,
+    isp: String,
+}
+
+#[test]
+fn test_derive_flattened_fields() {
+    assert_eq!(Flattened::FIELDS, Location::FIELDS | Renamed::FIELDS | FieldSet::from(Field::Isp));
+    assert_eq!(Flattened::FIELDS_PARAM, Flattened::FIELDS.with(Field::Message).to_string());
+}
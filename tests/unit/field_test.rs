@@ -0,0 +1,52 @@
+use ip_api4rs::field::{Field, FieldSet};
+use ip_api4rs::model::ip_response::IpFullResponse;
+use ip_api4rs::util::urls::{
+    build_batch_url_with_field_set, build_pro_batch_url_with_field_set, build_pro_url_with_field_set,
+    build_url_with_field_set,
+};
+use serde_aux::prelude::serde_introspect;
+
+use crate::constants::{TEST_API_KEY, TEST_IP};
+
+#[test]
+fn test_field_names() {
+    for field in Field::ALL {
+        assert_eq!(Field::from_name(field.name()), Some(field));
+    }
+    assert_eq!(Field::from_name("contry"), None);
+}
+
+#[test]
+fn test_all_fields_mask() {
+    assert_eq!(FieldSet::all().bits(), 66846719);
+    assert_eq!(FieldSet::from_bits(u32::MAX), FieldSet::all());
+}
+
+#[test]
+fn test_field_set_from_struct() {
+    let fields = FieldSet::from_names(serde_introspect::<IpFullResponse>()).unwrap();
+    assert_eq!(fields.with(Field::Message), FieldSet::all());
+    assert_eq!(FieldSet::from_names(&["country", "contry"]), None);
+}
+
+#[test]
+fn test_field_set_operations() {
+    let fields = Field::Country | Field::City | Field::Query;
+    assert_eq!(fields.bits(), 8209);
+    assert!(fields.contains(Field::City));
+    assert!(!fields.contains(Field::Zip));
+    assert_eq!(fields.fields(), vec![Field::Country, Field::City, Field::Query]);
+}
+
+#[test]
+fn test_field_set_urls() {
+    let fields = Field::Country | Field::City;
+    let url = build_url_with_field_set(false, TEST_IP, fields);
+    assert_eq!(url, format!("http://ip-api.com/json/{}?fields=32785", TEST_IP));
+    let url = build_batch_url_with_field_set(false, fields);
+    assert_eq!(url, "http://ip-api.com/batch?fields=32785");
+    let url = build_pro_url_with_field_set(TEST_IP, fields, TEST_API_KEY);
+    assert_eq!(url, format!("https://pro.ip-api.com/json/{}?fields=32785&key={}", TEST_IP, TEST_API_KEY));
+    let url = build_pro_batch_url_with_field_set(fields, TEST_API_KEY);
+    assert_eq!(url, format!("https://pro.ip-api.com/batch?fields=32785&key={}", TEST_API_KEY));
+}
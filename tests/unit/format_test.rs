@@ -0,0 +1,141 @@
+use crate::constants::{EXTERN_TEST_IP, TEST_IP};
+use crate::end2end::client::utils::get_expected_default_response;
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::field::Field;
+use ip_api4rs::format::{self, ResponseFormat};
+use ip_api4rs::model::ip_response::{IpDefaultResponse, IpFullResponse};
+use ip_api4rs::request_handler::process_result;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+const CSV_BODY: &str = "success,\"United States\",US,VA,Virginia,Ashburn,20149,39.03,-77.5,America/New_York,\
+                        \"Google LLC\",\"Google Public DNS\",\"AS15169 Google LLC\",8.8.8.8";
+
+const XML_BODY: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<query>
+  <status>success</status>
+  <country><![CDATA[United States]]></country>
+  <countryCode>US</countryCode>
+  <region>VA</region>
+  <regionName><![CDATA[Virginia]]></regionName>
+  <city><![CDATA[Ashburn]]></city>
+  <zip>20149</zip>
+  <lat>39.03</lat>
+  <lon>-77.5</lon>
+  <timezone>America/New_York</timezone>
+  <isp><![CDATA[Google LLC]]></isp>
+  <org><![CDATA[Google Public DNS]]></org>
+  <as><![CDATA[AS15169 Google LLC]]></as>
+  <query>8.8.8.8</query>
+</query>
+"#;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Flags {
+    mobile: bool,
+    offset: i32,
+    query: String,
+}
+
+fn decode_default(format: ResponseFormat, body: &str) -> Result<IpDefaultResponse, IpApiError> {
+    process_result::<IpDefaultResponse>(format::decode::<IpDefaultResponse>(format, body.to_string(), EXTERN_TEST_IP)?)
+}
+
+#[test]
+fn test_path() {
+    assert_eq!(ResponseFormat::default(), ResponseFormat::Json);
+    assert_eq!(ResponseFormat::Line.to_string(), "line");
+    assert_eq!(ResponseFormat::from_path("csv"), Some(ResponseFormat::Csv));
+    assert_eq!(ResponseFormat::from_path("yaml"), None);
+}
+
+#[test]
+fn test_media_type() {
+    assert_eq!(ResponseFormat::Json.media_type(), "application/json");
+    assert_eq!(ResponseFormat::Xml.media_type(), "application/xml");
+    assert_eq!(ResponseFormat::Csv.media_type(), "text/csv");
+    assert_eq!(ResponseFormat::Line.media_type(), "text/plain");
+}
+
+#[test]
+fn test_columns() {
+    assert_eq!(format::columns::<Flags>(), vec![Field::Offset, Field::Mobile, Field::Query]);
+    assert_eq!(format::columns::<IpDefaultResponse>(), Field::DEFAULT.to_vec());
+    assert_eq!(format::columns::<IpFullResponse>().len(), 24);
+}
+
+#[test]
+fn test_decode_csv() {
+    assert_eq!(decode_default(ResponseFormat::Csv, CSV_BODY).unwrap(), get_expected_default_response());
+}
+
+#[test]
+fn test_decode_line() {
+    let body = CSV_BODY.replace('"', "").replace(',', "\n") + "\n";
+    assert_eq!(decode_default(ResponseFormat::Line, &body).unwrap(), get_expected_default_response());
+}
+
+#[test]
+fn test_decode_xml() {
+    assert_eq!(decode_default(ResponseFormat::Xml, XML_BODY).unwrap(), get_expected_default_response());
+    let body = "<query><status>fail</status><message>a &amp; b</message><query/></query>";
+    let json = format::decode::<IpDefaultResponse>(ResponseFormat::Xml, body.to_string(), "").unwrap();
+    assert_eq!(json, r#"{"message":"a & b","query":"","status":"fail"}"#);
+}
+
+#[test]
+fn test_decode_typed_values() {
+    let json = format::decode::<Flags>(ResponseFormat::Csv, "-14400,true,8.8.8.8".to_string(), "").unwrap();
+    let flags = process_result::<Flags>(json).unwrap();
+    assert_eq!(
+        flags,
+        Flags {
+            mobile: true,
+            offset: -14400,
+            query: EXTERN_TEST_IP.to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_decode_failed_response() {
+    let body = format!("fail,reserved range,{}", TEST_IP);
+    let result = decode_default(ResponseFormat::Csv, &body);
+    assert!(matches!(result, Err(IpApiError::ReservedRange(response)) if response.query == TEST_IP));
+    let body = "fail\ninvalid query\nexample.invalid\n";
+    assert!(matches!(decode_default(ResponseFormat::Line, body), Err(IpApiError::InvalidQuery(_))));
+}
+
+#[test]
+fn test_decode_malformed_body() {
+    let result = format::decode::<IpDefaultResponse>(ResponseFormat::Csv, "<html>".to_string(), EXTERN_TEST_IP);
+    assert!(matches!(
+        result,
+        Err(IpApiError::MalformedBody {
+            format: ResponseFormat::Csv,
+            query: Some(_),
+            ..
+        })
+    ));
+    let result = format::decode::<IpDefaultResponse>(ResponseFormat::Xml, "<html>".to_string(), "");
+    assert!(matches!(
+        result,
+        Err(IpApiError::MalformedBody {
+            query: None,
+            ..
+        })
+    ));
+    let result = format::decode::<IpDefaultResponse>(ResponseFormat::Csv, "\"open".to_string(), "");
+    assert!(matches!(result, Err(IpApiError::MalformedBody { .. })));
+}
+
+#[test]
+fn test_encode_round_trip() {
+    let json = format::decode::<IpDefaultResponse>(ResponseFormat::Xml, XML_BODY.to_string(), "").unwrap();
+    let object = serde_json::from_str::<Map<String, Value>>(&json).unwrap();
+    assert_eq!(format::encode(ResponseFormat::Csv, &object), CSV_BODY);
+    for format in ResponseFormat::ALL {
+        let body = format::encode(format, &object);
+        assert_eq!(decode_default(format, &body).unwrap(), get_expected_default_response());
+    }
+}
@@ -0,0 +1,124 @@
+use ip_api4rs::field::{Field, FieldSet};
+use ip_api4rs::model::ip_response::IpFullResponse;
+use ip_api4rs::util::introspect::struct_fields;
+use ip_api4rs::util::urls::build_fields_param_from_struct;
+use serde::Deserialize;
+use serde_aux::prelude::serde_introspect;
+use std::net::IpAddr;
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Location {
+    lat: f32,
+    lon: f32,
+    #[serde(default)]
+    zip: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Network {
+    isp: String,
+    #[serde(rename = "as")]
+    asn: String,
+    mobile: bool,
+    offset: i32,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Flattened {
+    #[serde(rename = "query")]
+    ip: String,
+    #[serde(flatten)]
+    location: Location,
+    #[serde(flatten)]
+    network: Network,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Nested {
+    status: String,
+    #[serde(flatten)]
+    inner: Flattened,
+    #[serde(flatten)]
+    reverse: Reverse,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Reverse {
+    reverse: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(dead_code)]
+enum Status {
+    Success,
+    Fail,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Host {
+    query: IpAddr,
+    status: Status,
+    isp: String,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct TypedFlattened {
+    country: String,
+    #[serde(flatten)]
+    host: Host,
+    #[serde(flatten)]
+    location: Location,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct OptionalFlattened {
+    country: String,
+    #[serde(flatten)]
+    location: Option<Location>,
+    #[serde(flatten)]
+    reverse: Reverse,
+}
+
+fn field_set(names: Vec<&'static str>) -> FieldSet {
+    FieldSet::from_names(&names).unwrap()
+}
+
+#[test]
+fn test_plain_struct_fields() {
+    assert_eq!(struct_fields::<IpFullResponse>(), serde_introspect::<IpFullResponse>());
+}
+
+#[test]
+fn test_flattened_struct_fields() {
+    let expected = Field::Query | Field::Lat | Field::Lon | Field::Zip | Field::Isp | Field::As | Field::Mobile;
+    assert_eq!(field_set(struct_fields::<Flattened>()), expected | Field::Offset);
+    assert_eq!(build_fields_param_from_struct::<Flattened>(), (expected | Field::Offset | Field::Message).to_string());
+}
+
+#[test]
+fn test_nested_flattened_struct_fields() {
+    let fields = field_set(struct_fields::<Nested>());
+    assert_eq!(fields, field_set(struct_fields::<Flattened>()) | Field::Status | Field::Reverse);
+}
+
+#[test]
+fn test_flattened_typed_fields() {
+    let expected = Field::Country | Field::Query | Field::Status | Field::Isp | Field::Lat | Field::Lon | Field::Zip;
+    assert_eq!(field_set(struct_fields::<TypedFlattened>()), expected);
+    assert_eq!(build_fields_param_from_struct::<TypedFlattened>(), (expected | Field::Message).to_string());
+}
+
+#[test]
+fn test_flattened_option_fields() {
+    let expected = Field::Country | Field::Lat | Field::Lon | Field::Zip | Field::Reverse;
+    assert_eq!(field_set(struct_fields::<OptionalFlattened>()), expected);
+}
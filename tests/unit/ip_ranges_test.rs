@@ -0,0 +1,58 @@
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::util::ip_ranges::{check_query, classify, IpRange};
+use std::net::IpAddr;
+
+fn classify_str(ip: &str) -> Option<IpRange> {
+    classify(&ip.parse::<IpAddr>().unwrap())
+}
+
+#[test]
+fn test_private_ranges() {
+    for ip in ["10.1.2.3", "172.16.0.1", "172.31.255.255", "192.168.178.1", "fd12:3456::1", "::ffff:10.0.0.1"] {
+        assert_eq!(classify_str(ip), Some(IpRange::Private), "{}", ip);
+    }
+}
+
+#[test]
+fn test_reserved_ranges() {
+    for ip in [
+        "0.0.0.0",
+        "127.0.0.1",
+        "169.254.1.1",
+        "100.64.0.1",
+        "192.0.2.1",
+        "198.51.100.7",
+        "203.0.113.9",
+        "198.18.0.1",
+        "224.0.0.1",
+        "255.255.255.255",
+        "::",
+        "::1",
+        "fe80::1",
+        "2001:db8::1",
+        "ff02::1",
+    ] {
+        assert_eq!(classify_str(ip), Some(IpRange::Reserved), "{}", ip);
+    }
+}
+
+#[test]
+fn test_public_addresses() {
+    for ip in ["8.8.8.8", "172.32.0.1", "100.128.0.1", "1.1.1.1", "2001:4860:4860::8888", "::ffff:8.8.8.8"] {
+        assert_eq!(classify_str(ip), None, "{}", ip);
+    }
+}
+
+#[test]
+fn test_check_query() {
+    match check_query("127.0.0.1") {
+        Some(IpApiError::ReservedRange(response)) => {
+            assert_eq!(response.message, "reserved range");
+            assert_eq!(response.query, "127.0.0.1");
+        }
+        error => panic!("Wrong error type returned: {:?}", error),
+    }
+    assert!(matches!(check_query("192.168.0.1"), Some(IpApiError::PrivateRange(_))));
+    assert!(check_query("8.8.8.8").is_none());
+    assert!(check_query("example.com").is_none());
+}
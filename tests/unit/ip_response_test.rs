@@ -0,0 +1,92 @@
+use crate::constants::EXTERN_TEST_IP;
+use ip_api4rs::field::Field;
+use ip_api4rs::model::ip_response::{IpDefaultResponse, IpResponse, IpUniversalResponse};
+use serde_json::json;
+
+#[test]
+fn test_accessors() {
+    let response: IpResponse = serde_json::from_value(json!({
+        "status": "success",
+        "country": "United States",
+        "lat": 39.03,
+        "offset": -14400,
+        "hosting": true,
+        "query": EXTERN_TEST_IP,
+        "unknown": 1,
+    }))
+    .unwrap();
+    assert_eq!(response.status(), Some("success"));
+    assert_eq!(response.country(), Some("United States"));
+    assert_eq!(response.lat(), Some(39.03));
+    assert_eq!(response.offset(), Some(-14400));
+    assert_eq!(response.hosting(), Some(true));
+    assert_eq!(response.query(), Some(EXTERN_TEST_IP));
+    assert_eq!(response.isp(), None);
+    assert_eq!(response.get(Field::Country), Some(&json!("United States")));
+    assert_eq!(
+        response.fields(),
+        vec![Field::Status, Field::Country, Field::Lat, Field::Offset, Field::Hosting, Field::Query]
+    );
+    assert_eq!(response.values().len(), 7);
+}
+
+#[test]
+fn test_wrong_types() {
+    let response: IpResponse = serde_json::from_value(json!({"lat": "north", "mobile": "yes"})).unwrap();
+    assert_eq!(response.lat(), None);
+    assert_eq!(response.mobile(), None);
+}
+
+#[test]
+fn test_universal_optional_fields() {
+    let response: IpUniversalResponse = serde_json::from_value(json!({
+        "status": "success",
+        "country": "United States",
+        "lat": 39.03,
+        "offset": -14400,
+        "hosting": true,
+        "query": EXTERN_TEST_IP,
+        "unknown": 1,
+    }))
+    .unwrap();
+    assert_eq!(response.status.as_deref(), Some("success"));
+    assert_eq!(response.country.as_deref(), Some("United States"));
+    assert_eq!(response.lat, Some(39.03));
+    assert_eq!(response.offset, Some(-14400));
+    assert_eq!(response.hosting, Some(true));
+    assert_eq!(response.query.as_deref(), Some(EXTERN_TEST_IP));
+    assert_eq!(response.isp, None);
+    assert_eq!(
+        response.fields(),
+        vec![Field::Status, Field::Country, Field::Lat, Field::Offset, Field::Hosting, Field::Query]
+    );
+}
+
+#[test]
+fn test_universal_empty_strings_are_none() {
+    let response: IpUniversalResponse =
+        serde_json::from_value(json!({"district": "", "zip": "", "lat": "", "mobile": null, "isp": "Vodafone"}))
+            .unwrap();
+    assert_eq!(response.district, None);
+    assert_eq!(response.zip, None);
+    assert_eq!(response.lat, None);
+    assert_eq!(response.mobile, None);
+    assert_eq!(response.fields(), vec![Field::Isp]);
+}
+
+#[test]
+fn test_universal_wrong_types() {
+    assert!(serde_json::from_value::<IpUniversalResponse>(json!({"lat": "north"})).is_err());
+    assert!(serde_json::from_value::<IpUniversalResponse>(json!({"mobile": "yes"})).is_err());
+}
+
+#[test]
+fn test_universal_strict_variant() {
+    let partial = json!({"status": "success", "country": "United States", "query": EXTERN_TEST_IP});
+    assert!(serde_json::from_value::<IpDefaultResponse>(partial.clone()).is_err());
+    let response: IpUniversalResponse = serde_json::from_value(partial).unwrap();
+    assert_eq!(
+        serde_json::from_value::<IpUniversalResponse>(serde_json::to_value(&response).unwrap()).unwrap(),
+        response
+    );
+}
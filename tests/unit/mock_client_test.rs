@@ -0,0 +1,100 @@
+use crate::constants::{EXTERN_TEST_IP, TEST_IP};
+use crate::end2end::client::utils::get_expected_default_response;
+use ip_api4rs::client::{AsyncIpApi, BlockingIpApi};
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::language::Language;
+use ip_api4rs::model::ip_response::IpDefaultResponse;
+use ip_api4rs::test_util::mock_client::{MockCall, MockIpApiClient, MockMethod};
+use serde::Deserialize;
+use std::time::Duration;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct CountryResponse {
+    country: String,
+}
+
+fn build_mock() -> MockIpApiClient {
+    MockIpApiClient::new()
+        .response(EXTERN_TEST_IP, get_expected_default_response())
+        .fail(TEST_IP, "reserved range")
+        .error("1.1.1.1", || IpApiError::RateLimited {
+            retry_after: Duration::from_secs(30),
+        })
+}
+
+/// Stands in for downstream code that takes the client as a dependency.
+async fn lookup_country(client: &impl AsyncIpApi, ip: &str) -> Result<String, IpApiError> {
+    Ok(client.query_api::<CountryResponse>(ip).await?.country)
+}
+
+#[tokio::test]
+async fn test_async_answers() {
+    let mock = build_mock();
+    assert_eq!(AsyncIpApi::query_api_default(&mock, EXTERN_TEST_IP).await.unwrap(), get_expected_default_response());
+    assert_eq!(lookup_country(&mock, EXTERN_TEST_IP).await.unwrap(), "United States");
+    assert!(matches!(lookup_country(&mock, TEST_IP).await, Err(IpApiError::ReservedRange(_))));
+    assert!(matches!(lookup_country(&mock, "1.1.1.1").await, Err(IpApiError::RateLimited { .. })));
+    assert!(matches!(lookup_country(&mock, "unknown").await, Err(IpApiError::InvalidQuery(_))));
+    assert!(matches!(AsyncIpApi::query_api_fully(&mock, EXTERN_TEST_IP).await, Err(IpApiError::JsonParseError(_))));
+}
+
+#[tokio::test]
+async fn test_async_batch() {
+    let mock = build_mock();
+    let results = AsyncIpApi::query_api_batch::<IpDefaultResponse>(&mock, &[EXTERN_TEST_IP, TEST_IP]).await.unwrap();
+    assert_eq!(results[0].as_ref().unwrap(), &get_expected_default_response());
+    assert!(matches!(results[1], Err(IpApiError::ReservedRange(_))));
+    let queries = vec![EXTERN_TEST_IP; 101];
+    let result = AsyncIpApi::query_api_batch::<IpDefaultResponse>(&mock, &queries).await;
+    assert!(matches!(result, Err(IpApiError::BatchTooLarge(101))));
+    let results = AsyncIpApi::query_api_bulk::<IpDefaultResponse, _>(&mock, queries).await.unwrap();
+    assert_eq!(results.len(), 101);
+}
+
+#[test]
+fn test_bulk_records_and_validates() {
+    let mock = build_mock();
+    let results = BlockingIpApi::query_api_bulk::<CountryResponse, _>(&mock, [EXTERN_TEST_IP, "Invalid Query/?"]);
+    let results = results.unwrap();
+    assert_eq!(results[0].as_ref().unwrap().country, "United States");
+    assert!(matches!(results[1], Err(IpApiError::InvalidQuery(_))));
+    assert_eq!(
+        mock.calls(),
+        vec![MockCall {
+            method: MockMethod::Bulk,
+            queries: vec![EXTERN_TEST_IP.to_string(), "Invalid Query/?".to_string()],
+            language: None,
+        }]
+    );
+}
+
+#[test]
+fn test_blocking_answers_and_calls() {
+    let mock = build_mock();
+    assert!(BlockingIpApi::query_api_default(&mock, EXTERN_TEST_IP).is_ok());
+    let result = BlockingIpApi::query_api_localized::<CountryResponse>(&mock, EXTERN_TEST_IP, Language::German);
+    assert_eq!(result.unwrap().country, "United States");
+    assert!(BlockingIpApi::query_api_batch::<CountryResponse>(&mock, &[TEST_IP]).is_ok());
+    assert_eq!(
+        mock.calls(),
+        vec![
+            MockCall {
+                method: MockMethod::Default,
+                queries: vec![EXTERN_TEST_IP.to_string()],
+                language: None,
+            },
+            MockCall {
+                method: MockMethod::Localized,
+                queries: vec![EXTERN_TEST_IP.to_string()],
+                language: Some(Language::German),
+            },
+            MockCall {
+                method: MockMethod::Batch,
+                queries: vec![TEST_IP.to_string()],
+                language: None,
+            },
+        ]
+    );
+    mock.clear_calls();
+    assert_eq!(mock.call_count(), 0);
+}
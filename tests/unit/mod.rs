@@ -1 +1,16 @@
+pub mod builder_test;
+pub mod cache_test;
+#[cfg(feature = "derive")]
+pub mod derive_test;
+pub mod field_test;
+pub mod format_test;
+pub mod introspect_test;
+pub mod ip_ranges_test;
+pub mod ip_response_test;
+#[cfg(feature = "test-util")]
+pub mod mock_client_test;
+pub mod query_test;
+pub mod rate_limit_test;
+pub mod requests_test;
+pub mod retry_test;
 pub mod urls_test;
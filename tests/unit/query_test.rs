@@ -0,0 +1,40 @@
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::query::{IntoQuery, Query};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+#[test]
+fn test_parse_ip() {
+    assert_eq!(Query::parse("8.8.8.8").unwrap(), Query::Ip(IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8))));
+    assert_eq!(Query::parse("2001:4860:4860::8888").unwrap().to_string(), "2001:4860:4860::8888");
+    assert_eq!(Query::from(Ipv6Addr::LOCALHOST), Query::Ip(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+    assert_eq!(Ipv4Addr::new(1, 1, 1, 1).into_query().unwrap().ip(), Some(IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1))));
+}
+
+#[test]
+fn test_parse_host() {
+    assert_eq!(Query::parse("example.com").unwrap(), Query::Host("example.com".to_string()));
+    assert_eq!(Query::parse("dns.google.").unwrap().to_string(), "dns.google.");
+    assert!(Query::host("my-host").is_ok());
+    assert_eq!(Query::parse("example.com").unwrap().ip(), None);
+}
+
+#[test]
+fn test_parse_self() {
+    assert_eq!(Query::parse("").unwrap(), Query::SelfIp);
+    assert_eq!(Query::SelfIp.to_string(), "");
+}
+
+#[test]
+fn test_parse_invalid() {
+    for query in ["Invalid Query", "-example.com", "example..com", "exa_mple.com", "8.8.8.8/json", "a?b=c"] {
+        match Query::parse(query) {
+            Err(IpApiError::InvalidQuery(response)) => {
+                assert_eq!(response.message, "invalid query");
+                assert_eq!(response.query, query);
+            }
+            result => panic!("{} was not rejected: {:?}", query, result),
+        }
+    }
+    assert!(Query::host("a".repeat(64)).is_err());
+    assert!("Invalid Query".to_string().into_query().is_err());
+}
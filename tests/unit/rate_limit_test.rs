@@ -0,0 +1,45 @@
+use ip_api4rs::util::rate_limit::ServerRateLimit;
+use reqwest::header::HeaderMap;
+use std::time::Duration;
+
+fn build_headers(requests_left: &str, time_to_reset: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Rl", requests_left.parse().unwrap());
+    headers.insert("X-Ttl", time_to_reset.parse().unwrap());
+    headers
+}
+
+#[test]
+fn test_requests_left() {
+    let server_limit = ServerRateLimit::new();
+    server_limit.update(&build_headers("44", "60"));
+    assert_eq!(server_limit.remaining(), Some(44));
+    assert_eq!(server_limit.time_until_ready(), None);
+}
+
+#[test]
+fn test_window_exhausted() {
+    let server_limit = ServerRateLimit::new();
+    server_limit.update(&build_headers("0", "30"));
+    assert_eq!(server_limit.remaining(), Some(0));
+    let wait = server_limit.time_until_ready().unwrap();
+    assert!(wait > Duration::from_secs(29) && wait <= Duration::from_secs(30));
+}
+
+#[test]
+fn test_missing_headers() {
+    let server_limit = ServerRateLimit::new();
+    server_limit.update(&build_headers("0", "30"));
+    server_limit.update(&HeaderMap::new());
+    assert_eq!(server_limit.remaining(), Some(0));
+    assert!(server_limit.time_until_ready().is_some());
+}
+
+#[test]
+fn test_block_for() {
+    let server_limit = ServerRateLimit::new();
+    server_limit.block_for(Duration::from_secs(10));
+    assert_eq!(server_limit.remaining(), Some(0));
+    let wait = server_limit.time_until_ready().unwrap();
+    assert!(wait > Duration::from_secs(9) && wait <= Duration::from_secs(10));
+}
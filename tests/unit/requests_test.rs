@@ -0,0 +1,92 @@
+use crate::constants::{TEST_API_KEY, TEST_IP};
+use ip_api4rs::format::ResponseFormat;
+use ip_api4rs::language::Language;
+use ip_api4rs::model::ip_response::IpDefaultResponse;
+use ip_api4rs::util::requests::{
+    get_async_batch_request, get_async_format_request, get_async_localized_request, get_async_request,
+};
+use ip_api4rs::IpApiClient;
+
+const DEFAULT_FIELDS: &str = "61439";
+
+#[test]
+fn test_free_tier_request() {
+    let client = IpApiClient::new();
+    let request = get_async_request::<IpDefaultResponse>(TEST_IP, &client).build().unwrap();
+    assert_eq!(request.url().as_str(), format!("http://ip-api.com/json/{}?fields={}", TEST_IP, DEFAULT_FIELDS));
+    assert!(request.headers().get("Authorization").is_none());
+}
+
+#[test]
+fn test_pro_tier_request() {
+    let client = IpApiClient::new_with_api_key(TEST_API_KEY.to_string());
+    let request = get_async_request::<IpDefaultResponse>(TEST_IP, &client).build().unwrap();
+    assert_eq!(
+        request.url().as_str(),
+        format!("https://pro.ip-api.com/json/{}?fields={}&key={}", TEST_IP, DEFAULT_FIELDS, TEST_API_KEY)
+    );
+    assert!(request.headers().get("Authorization").is_none());
+}
+
+#[test]
+fn test_pro_tier_batch_request() {
+    let client = IpApiClient::new_with_api_key(TEST_API_KEY.to_string());
+    let request = get_async_batch_request::<IpDefaultResponse>(&[TEST_IP], &client).build().unwrap();
+    assert_eq!(
+        request.url().as_str(),
+        format!("https://pro.ip-api.com/batch?fields={}&key={}", DEFAULT_FIELDS, TEST_API_KEY)
+    );
+}
+
+#[test]
+fn test_default_language_request() {
+    let mut client = IpApiClient::new();
+    client.language = Some(Language::German);
+    let request = get_async_request::<IpDefaultResponse>(TEST_IP, &client).build().unwrap();
+    assert_eq!(request.url().as_str(), format!("http://ip-api.com/json/{}?fields={}&lang=de", TEST_IP, DEFAULT_FIELDS));
+}
+
+#[test]
+fn test_overridden_language_request() {
+    let mut client = IpApiClient::new();
+    client.language = Some(Language::German);
+    let request = get_async_localized_request::<IpDefaultResponse>(TEST_IP, Some(Language::PortugueseBrazil), &client)
+        .build()
+        .unwrap();
+    assert_eq!(
+        request.url().as_str(),
+        format!("http://ip-api.com/json/{}?fields={}&lang=pt-BR", TEST_IP, DEFAULT_FIELDS)
+    );
+}
+
+#[test]
+fn test_base_url_request() {
+    let client = IpApiClient::new().with_base_url("http://localhost:8080/");
+    let request = get_async_request::<IpDefaultResponse>(TEST_IP, &client).build().unwrap();
+    assert_eq!(request.url().as_str(), format!("http://localhost:8080/json/{}?fields={}", TEST_IP, DEFAULT_FIELDS));
+    let request = get_async_batch_request::<IpDefaultResponse>(&[TEST_IP], &client).build().unwrap();
+    assert_eq!(request.url().as_str(), format!("http://localhost:8080/batch?fields={}", DEFAULT_FIELDS));
+}
+
+#[test]
+fn test_base_url_pro_tier_request() {
+    let client = IpApiClient::new_with_api_key(TEST_API_KEY.to_string()).with_base_url("http://localhost:8080");
+    let request = get_async_request::<IpDefaultResponse>(TEST_IP, &client).build().unwrap();
+    assert_eq!(
+        request.url().as_str(),
+        format!("http://localhost:8080/json/{}?fields={}&key={}", TEST_IP, DEFAULT_FIELDS, TEST_API_KEY)
+    );
+}
+
+#[test]
+fn test_accept_header_request() {
+    let client = IpApiClient::new();
+    let request = get_async_request::<IpDefaultResponse>(TEST_IP, &client).build().unwrap();
+    assert_eq!(request.headers().get("Accept").unwrap(), "application/json");
+    for format in ResponseFormat::ALL {
+        let request = get_async_format_request::<IpDefaultResponse>(TEST_IP, None, format, &client).build().unwrap();
+        assert_eq!(request.headers().get("Accept").unwrap(), format.media_type());
+    }
+    let request = get_async_batch_request::<IpDefaultResponse>(&[TEST_IP], &client).build().unwrap();
+    assert_eq!(request.headers().get("Accept").unwrap(), "application/json");
+}
@@ -0,0 +1,95 @@
+use ip_api4rs::blocking::client::BlockingIpApiClient;
+use ip_api4rs::client::{AsyncIpApi, BlockingIpApi};
+use ip_api4rs::error::IpApiError;
+use ip_api4rs::model::ip_response::{ErrorResponse, IpDefaultResponse};
+use ip_api4rs::util::retry::RetryPolicy;
+use ip_api4rs::IpApiClient;
+use std::time::{Duration, Instant};
+
+/// A base url nothing listens on, so every request fails with a connect error.
+const UNREACHABLE_BASE_URL: &str = "http://127.0.0.1:9";
+
+fn build_policy(max_attempts: u32, base_delay: Duration) -> RetryPolicy {
+    RetryPolicy {
+        jitter: false,
+        ..RetryPolicy::new(max_attempts, base_delay)
+    }
+}
+
+fn build_error_response(message: &str) -> ErrorResponse {
+    ErrorResponse {
+        status: "fail".to_string(),
+        message: message.to_string(),
+        query: "127.0.0.1".to_string(),
+    }
+}
+
+#[test]
+fn test_default_does_not_retry() {
+    let error = IpApiError::RateLimited {
+        retry_after: Duration::from_secs(1),
+    };
+    assert_eq!(RetryPolicy::default().delay_for(&error, 1), None);
+}
+
+#[test]
+fn test_rate_limited_waits_for_window() {
+    let policy = build_policy(3, Duration::from_millis(100));
+    let error = IpApiError::RateLimited {
+        retry_after: Duration::from_secs(7),
+    };
+    assert_eq!(policy.delay_for(&error, 1), Some(Duration::from_secs(7)));
+    assert_eq!(policy.delay_for(&error, 3), None);
+    let policy = RetryPolicy {
+        retry_rate_limited: false,
+        ..policy
+    };
+    assert_eq!(policy.delay_for(&error, 1), None);
+}
+
+#[test]
+fn test_api_errors_are_not_retried() {
+    let policy = build_policy(5, Duration::from_millis(100));
+    assert!(!policy.is_retryable(&IpApiError::ReservedRange(build_error_response("reserved range"))));
+    assert!(!policy.is_retryable(&IpApiError::InvalidQuery(build_error_response("invalid query"))));
+    assert!(!policy.is_retryable(&IpApiError::BatchTooLarge(101)));
+    assert!(!policy.is_retryable(&IpApiError::Unknown("unknown".to_string())));
+}
+
+#[tokio::test]
+async fn test_exponential_backoff() {
+    let client = IpApiClient::builder()
+        .base_url(UNREACHABLE_BASE_URL)
+        .retry_policy(build_policy(3, Duration::from_millis(50)))
+        .build()
+        .unwrap();
+    let start = Instant::now();
+    let result = client.query_api_default("1.1.1.1").await;
+    assert!(matches!(result, Err(IpApiError::ReqwestError(ref err)) if err.is_connect()));
+    assert!(start.elapsed() >= Duration::from_millis(150));
+}
+
+#[test]
+fn test_blocking_exponential_backoff() {
+    let client = BlockingIpApiClient::builder()
+        .base_url(UNREACHABLE_BASE_URL)
+        .retry_policy(build_policy(3, Duration::from_millis(50)))
+        .build_blocking()
+        .unwrap();
+    let start = Instant::now();
+    let result = client.query_api::<IpDefaultResponse>("1.1.1.1");
+    assert!(matches!(result, Err(IpApiError::ReqwestError(ref err)) if err.is_connect()));
+    assert!(start.elapsed() >= Duration::from_millis(150));
+}
+
+#[tokio::test]
+async fn test_jitter_keeps_at_least_half_the_delay() {
+    let client = IpApiClient::builder()
+        .base_url(UNREACHABLE_BASE_URL)
+        .retry_policy(RetryPolicy::new(3, Duration::from_millis(50)))
+        .build()
+        .unwrap();
+    let start = Instant::now();
+    assert!(client.query_api_default("1.1.1.1").await.is_err());
+    assert!(start.elapsed() >= Duration::from_millis(75));
+}
@@ -1,27 +1,130 @@
-use crate::constants::TEST_IP;
+use crate::constants::{TEST_API_KEY, TEST_IP};
+use ip_api4rs::constant::{HTTP_BATCH_HOST, PRO_BATCH_HOST, PRO_HOST};
+use ip_api4rs::language::Language;
 use ip_api4rs::model::ip_response::IpFullResponse;
-use ip_api4rs::util::urls::{build_http_url_from_struct, build_https_url_from_struct, build_url_without_fields};
+use ip_api4rs::util::urls::{
+    append_language, build_batch_url_from_struct, build_batch_url_on_host, build_batch_url_with_fields,
+    build_edns_host, build_edns_url, build_http_url_from_struct, build_https_url_from_struct,
+    build_pro_batch_url_from_struct, build_pro_batch_url_with_fields, build_pro_url_from_struct,
+    build_pro_url_with_fields, build_pro_url_without_fields, build_url_on_host, build_url_with_fields,
+    build_url_without_fields, percent_decode, percent_encode, query_from_url,
+};
 
 #[test]
 fn test_default_http_url() {
-    let url = build_url_without_fields(false, &TEST_IP.to_string());
+    let url = build_url_without_fields(false, TEST_IP);
     assert_eq!(url, format!("http://ip-api.com/json/{}", TEST_IP))
 }
 
 #[test]
 fn test_default_https_url() {
-    let url = build_url_without_fields(true, &TEST_IP.to_string());
+    let url = build_url_without_fields(true, TEST_IP);
     assert_eq!(url, format!("https://ip-api.com/json/{}", TEST_IP))
 }
 
 #[test]
 fn test_custom_http_url() {
-    let url = build_http_url_from_struct::<IpFullResponse>(&TEST_IP.to_string());
-    assert_eq!(url, format!("http://ip-api.com/json/{}?fields=query,status,continent,continentCode,country,countryCode,region,regionName,city,district,zip,lat,lon,timezone,offset,currency,isp,org,as,asname,reverse,mobile,proxy,hosting,message", TEST_IP));
+    let url = build_http_url_from_struct::<IpFullResponse>(TEST_IP);
+    assert_eq!(url, format!("http://ip-api.com/json/{}?fields=66846719", TEST_IP));
 }
 
 #[test]
 fn test_custom_https_url() {
-    let url = build_https_url_from_struct::<IpFullResponse>(&TEST_IP.to_string());
-    assert_eq!(url, format!("https://ip-api.com/json/{}?fields=query,status,continent,continentCode,country,countryCode,region,regionName,city,district,zip,lat,lon,timezone,offset,currency,isp,org,as,asname,reverse,mobile,proxy,hosting,message", TEST_IP));
+    let url = build_https_url_from_struct::<IpFullResponse>(TEST_IP);
+    assert_eq!(url, format!("https://ip-api.com/json/{}?fields=66846719", TEST_IP));
+}
+
+#[test]
+fn test_batch_http_url() {
+    let url = build_batch_url_from_struct::<IpFullResponse>(false);
+    assert_eq!(url, "http://ip-api.com/batch?fields=66846719");
+}
+
+#[test]
+fn test_default_pro_url() {
+    let url = build_pro_url_without_fields(TEST_IP, TEST_API_KEY);
+    assert_eq!(url, format!("https://pro.ip-api.com/json/{}?key={}", TEST_IP, TEST_API_KEY))
+}
+
+#[test]
+fn test_custom_pro_url() {
+    let url = build_pro_url_from_struct::<IpFullResponse>(TEST_IP, TEST_API_KEY);
+    assert_eq!(url, format!("https://pro.ip-api.com/json/{}?fields=66846719&key={}", TEST_IP, TEST_API_KEY));
+}
+
+#[test]
+fn test_batch_pro_url() {
+    let url = build_pro_batch_url_from_struct::<IpFullResponse>(TEST_API_KEY);
+    assert_eq!(url, format!("https://pro.ip-api.com/batch?fields=66846719&key={}", TEST_API_KEY));
+}
+
+#[test]
+fn test_url_on_host() {
+    let url = build_url_on_host(PRO_HOST, TEST_IP, "61439", Some(TEST_API_KEY));
+    assert_eq!(url, format!("https://pro.ip-api.com/json/{}?fields=61439&key={}", TEST_IP, TEST_API_KEY));
+    let url = build_batch_url_on_host(HTTP_BATCH_HOST, "61439", None);
+    assert_eq!(url, "http://ip-api.com/batch?fields=61439");
+    let url = build_batch_url_on_host(PRO_BATCH_HOST, "61439", Some(TEST_API_KEY));
+    assert_eq!(url, format!("https://pro.ip-api.com/batch?fields=61439&key={}", TEST_API_KEY));
+}
+
+#[test]
+fn test_encoded_api_key() {
+    let url = build_url_on_host(PRO_HOST, TEST_IP, "61439", Some("a&b=c d"));
+    assert_eq!(url, format!("https://pro.ip-api.com/json/{}?fields=61439&key=a%26b%3Dc%20d", TEST_IP));
+    let url = build_pro_url_without_fields(TEST_IP, "a&lang=de");
+    assert_eq!(url, format!("https://pro.ip-api.com/json/{}?key=a%26lang%3Dde", TEST_IP));
+}
+
+#[test]
+fn test_url_with_fields() {
+    let url = build_url_with_fields(false, TEST_IP, &["country", "city"]);
+    assert_eq!(url, format!("http://ip-api.com/json/{}?fields=country,city,message", TEST_IP));
+    let url = build_pro_url_with_fields(TEST_IP, &["country"], TEST_API_KEY);
+    assert_eq!(url, format!("https://pro.ip-api.com/json/{}?fields=country,message&key={}", TEST_IP, TEST_API_KEY));
+    let url = build_batch_url_with_fields(true, &["country"]);
+    assert_eq!(url, "https://ip-api.com/batch?fields=country,message");
+    let url = build_pro_batch_url_with_fields(&["country"], TEST_API_KEY);
+    assert_eq!(url, format!("https://pro.ip-api.com/batch?fields=country,message&key={}", TEST_API_KEY));
+    let url = build_url_without_fields(false, "example host");
+    assert_eq!(url, "http://ip-api.com/json/example%20host");
+}
+
+#[test]
+fn test_localized_url() {
+    let url = append_language(build_url_without_fields(false, TEST_IP), Language::German);
+    assert_eq!(url, format!("http://ip-api.com/json/{}?lang=de", TEST_IP));
+    let url = append_language(build_pro_url_without_fields(TEST_IP, TEST_API_KEY), Language::Japanese);
+    assert_eq!(url, format!("https://pro.ip-api.com/json/{}?key={}&lang=ja", TEST_IP, TEST_API_KEY));
+}
+
+#[test]
+fn test_query_from_url() {
+    let url = reqwest::Url::parse("http://ip-api.com/json/example%20host?fields=61439").unwrap();
+    assert_eq!(query_from_url(&url), Some("example host".to_string()));
+    let url = reqwest::Url::parse("http://ip-api.com/json/?fields=61439").unwrap();
+    assert_eq!(query_from_url(&url), None);
+    let url = reqwest::Url::parse("http://ip-api.com/csv/8.8.8.8?fields=61439").unwrap();
+    assert_eq!(query_from_url(&url), Some("8.8.8.8".to_string()));
+    let url = reqwest::Url::parse("http://ip-api.com/batch").unwrap();
+    assert_eq!(query_from_url(&url), None);
+}
+
+#[test]
+fn test_percent_decode() {
+    assert_eq!(percent_decode("2001%3Adb8%3A%3A1"), "2001:db8::1");
+    assert_eq!(percent_decode("100%"), "100%");
+}
+
+#[test]
+fn test_percent_encode() {
+    assert_eq!(percent_encode("2001:db8::1"), "2001:db8::1");
+    assert_eq!(percent_encode("Invalid Query/?"), "Invalid%20Query%2F%3F");
+    assert_eq!(percent_decode(&percent_encode("a b&c")), "a b&c");
+}
+
+#[test]
+fn test_edns_url() {
+    assert_eq!(build_edns_url("abc123"), "http://abc123.edns.ip-api.com/json");
+    assert_eq!(build_edns_host("http://localhost:8080/"), "http://localhost:8080/json");
 }